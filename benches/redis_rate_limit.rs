@@ -0,0 +1,108 @@
+//! Throughput of the fixed-window rate limit script against a live Redis,
+//! comparing the old store layout (connection behind a mutex, `Script`
+//! rebuilt per call) with the current one (cloned multiplexed connection,
+//! script built once and preloaded).
+//!
+//! Run with `cargo bench --bench redis_rate_limit`. Uses `REDIS_URL`
+//! (default `redis://127.0.0.1:6379`) and is skipped if Redis is unreachable.
+
+use redis::aio::MultiplexedConnection;
+use redis::Script;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+const SCRIPT: &str = include_str!("../src/infrastructure/rate_limit/lua/fixed_window.lua");
+const TASKS: usize = 64;
+const CHECKS_PER_TASK: usize = 500;
+const WINDOW_MS: i64 = 60_000;
+
+type Check = (i64, i64, i64, i64);
+
+async fn locked_rebuilt(conn: Arc<Mutex<MultiplexedConnection>>, task: usize) {
+    for i in 0..CHECKS_PER_TASK {
+        let script = Script::new(SCRIPT);
+        let mut conn = conn.lock().await;
+
+        let _: Check = script
+            .key(format!("bench:locked:{task}:{}", i % 16))
+            .arg(u32::MAX)
+            .arg(WINDOW_MS)
+            .invoke_async(&mut *conn)
+            .await
+            .expect("script failed");
+    }
+}
+
+async fn cloned_cached(conn: MultiplexedConnection, script: Arc<Script>, task: usize) {
+    for i in 0..CHECKS_PER_TASK {
+        let mut conn = conn.clone();
+
+        let _: Check = script
+            .key(format!("bench:cloned:{task}:{}", i % 16))
+            .arg(u32::MAX)
+            .arg(WINDOW_MS)
+            .invoke_async(&mut conn)
+            .await
+            .expect("script failed");
+    }
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let total = (TASKS * CHECKS_PER_TASK) as f64;
+
+    println!(
+        "{name:<28} {total:>8} checks in {:>8.2?}  {:>10.0} checks/s",
+        elapsed,
+        total / elapsed.as_secs_f64()
+    );
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build runtime");
+
+    runtime.block_on(async {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
+
+        let conn = match redis::Client::open(url.as_str()) {
+            Ok(client) => client.get_multiplexed_async_connection().await,
+            Err(err) => Err(err),
+        };
+
+        let conn = match conn {
+            Ok(conn) => conn,
+            Err(err) => {
+                eprintln!("skipping redis_rate_limit bench: {err}");
+                return;
+            }
+        };
+
+        let script = Arc::new(Script::new(SCRIPT));
+        script
+            .load_async(&mut conn.clone())
+            .await
+            .expect("failed to load script");
+
+        let locked = Arc::new(Mutex::new(conn.clone()));
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..TASKS)
+            .map(|task| tokio::spawn(locked_rebuilt(locked.clone(), task)))
+            .collect();
+        for task in tasks {
+            task.await.expect("bench task panicked");
+        }
+        report("mutex + script per call", start.elapsed());
+
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..TASKS)
+            .map(|task| tokio::spawn(cloned_cached(conn.clone(), script.clone(), task)))
+            .collect();
+        for task in tasks {
+            task.await.expect("bench task panicked");
+        }
+        report("cloned conn + cached script", start.elapsed());
+    });
+}
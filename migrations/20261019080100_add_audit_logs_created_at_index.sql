@@ -0,0 +1,2 @@
+-- Add migration script here
+CREATE INDEX idx_audit_logs_created_at ON audit_logs(created_at);
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::domain::audit::{entity::AuditLog, repository::AuditLogRepository};
+use crate::shared::request_id;
 
 pub struct AuditLogger {
     pub(crate) repo: Arc<dyn AuditLogRepository>,
@@ -13,13 +14,18 @@ impl AuditLogger {
         Self { repo }
     }
 
+    /// Adds the current request id to object `metadata`.
     pub async fn log(
         &self,
         actor_id: Option<Uuid>,
         action: &str,
         resource: &str,
-        metadata: serde_json::Value,
+        mut metadata: serde_json::Value,
     ) {
+        if let (Some(id), Some(fields)) = (request_id::current(), metadata.as_object_mut()) {
+            fields.insert("request_id".to_string(), id.to_string().into());
+        }
+
         let log = AuditLog {
             id: Uuid::now_v7(),
             actor_id,
@@ -32,3 +38,34 @@ impl AuditLogger {
         self.repo.store(log).await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::http::test_support::RecordedAuditLogs;
+
+    #[tokio::test]
+    async fn object_metadata_gets_the_request_id() {
+        let repo = Arc::new(RecordedAuditLogs::default());
+        let logger = AuditLogger::new(repo.clone());
+        let id = Uuid::now_v7();
+
+        request_id::scope(id, async {
+            logger.log(None, "login", "user", json!({ "ip": "203.0.113.9" })).await;
+            logger.log(None, "login", "user", json!("legacy")).await;
+        })
+        .await;
+        logger.log(None, "login", "user", json!({})).await;
+
+        let logs = repo.logs.lock().unwrap();
+
+        assert_eq!(
+            logs[0].metadata,
+            json!({ "ip": "203.0.113.9", "request_id": id.to_string() })
+        );
+        assert_eq!(logs[1].metadata, json!("legacy"));
+        assert_eq!(logs[2].metadata, json!({}));
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::domain::audit::{
+    entity::AuditLog, filter::AuditLogFilter, repository::AuditLogRepository,
+};
+
+pub const MAX_PER_PAGE: u32 = 100;
+
+#[derive(Debug, Error)]
+pub enum ListAuditLogsError {
+    #[error("unexpected error")]
+    Unexpected,
+}
+
+pub struct ListAuditLogsQuery {
+    pub filter: AuditLogFilter,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+pub struct AuditLogPage {
+    pub items: Vec<AuditLog>,
+    pub page: u32,
+    pub per_page: u32,
+    pub total: u64,
+}
+
+pub struct ListAuditLogsUseCase {
+    repo: Arc<dyn AuditLogRepository>,
+}
+
+impl ListAuditLogsUseCase {
+    pub fn new(repo: Arc<dyn AuditLogRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        query: ListAuditLogsQuery,
+    ) -> Result<AuditLogPage, ListAuditLogsError> {
+        let page = query.page.max(1);
+        let per_page = query.per_page.clamp(1, MAX_PER_PAGE);
+        let offset = u64::from(page - 1) * u64::from(per_page);
+
+        let total = self
+            .repo
+            .count(&query.filter)
+            .await
+            .map_err(|_| ListAuditLogsError::Unexpected)?;
+
+        let items = self
+            .repo
+            .find(&query.filter, per_page, offset)
+            .await
+            .map_err(|_| ListAuditLogsError::Unexpected)?;
+
+        Ok(AuditLogPage {
+            items,
+            page,
+            per_page,
+            total,
+        })
+    }
+}
@@ -1 +1,3 @@
 pub mod audit_logger;
+pub mod export_audit_logs;
+pub mod list_audit_logs;
@@ -0,0 +1,170 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use thiserror::Error;
+
+use crate::domain::rate_limit::algorithm::RateLimitAlgorithm;
+use crate::domain::rate_limit::store::RateLimitStore;
+use crate::domain::user::value_objects::UserEmail;
+
+const GLOBAL_FAILURES_KEY: &str = "login:failures:global";
+
+#[derive(Debug, Error)]
+pub enum LoginThrottleError {
+    #[error("too many login attempts for this account")]
+    Throttled { retry_after: Option<Duration> },
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginThrottleConfig {
+    /// Attempts allowed per account and window in normal operation.
+    pub account_limit: u32,
+    /// Attempts allowed per account and window while the circuit is tripped.
+    pub strict_account_limit: u32,
+    pub account_window: Duration,
+    /// Failed logins across all accounts that trip the circuit.
+    pub failure_threshold: u32,
+    pub failure_window: Duration,
+    /// How long strict mode lasts once tripped.
+    pub strict_cooldown: Duration,
+}
+
+/// Guards `/login` against attacks spread over many IPs: attempts are
+/// counted per target account, and a spike in failures across all accounts
+/// switches every account to a stricter quota for a while.
+pub struct LoginThrottle {
+    store: Arc<dyn RateLimitStore>,
+    config: LoginThrottleConfig,
+    /// Unix millis until which strict mode is active.
+    strict_until: AtomicI64,
+}
+
+impl LoginThrottle {
+    pub fn new(store: Arc<dyn RateLimitStore>, config: LoginThrottleConfig) -> Self {
+        Self {
+            store,
+            config,
+            strict_until: AtomicI64::new(0),
+        }
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict_until.load(Ordering::Relaxed) > Utc::now().timestamp_millis()
+    }
+
+    /// Counts an attempt against `email` and rejects it once the account's
+    /// quota is spent. Storage errors let the attempt through; the per-IP
+    /// limits still apply.
+    pub async fn check_account(&self, email: &str) -> Result<(), LoginThrottleError> {
+        let limit = if self.is_strict() {
+            self.config.strict_account_limit
+        } else {
+            self.config.account_limit
+        };
+
+        match self
+            .store
+            .check(account_key(email), limit, self.config.account_window, RateLimitAlgorithm::SlidingWindow)
+            .await
+        {
+            Ok(decision) if !decision.allowed => Err(LoginThrottleError::Throttled {
+                retry_after: decision.retry_after,
+            }),
+            Ok(_) => Ok(()),
+            Err(_) => {
+                tracing::warn!("login throttle store unavailable, skipping account check");
+                Ok(())
+            }
+        }
+    }
+
+    /// Clears the account's attempts after a successful login, so only
+    /// failures accumulate: neither a user who logs in often nor someone
+    /// who merely knows the email can use up the quota with valid logins.
+    pub async fn reset_account(&self, email: &str) {
+        if self
+            .store
+            .reset(account_key(email), RateLimitAlgorithm::SlidingWindow)
+            .await
+            .is_err()
+        {
+            tracing::warn!("login throttle store unavailable, account attempts not reset");
+        }
+    }
+
+    /// The failure count lives in the shared store, so every instance that
+    /// sees failures during a spike trips its own circuit.
+    pub async fn record_failure(&self) {
+        let decision = self
+            .store
+            .check(
+                GLOBAL_FAILURES_KEY.to_string(),
+                self.config.failure_threshold,
+                self.config.failure_window,
+                RateLimitAlgorithm::FixedWindow,
+            )
+            .await;
+
+        if let Ok(decision) = decision
+            && !decision.allowed
+        {
+            let until = Utc::now().timestamp_millis() + self.config.strict_cooldown.as_millis() as i64;
+            let previous = self.strict_until.fetch_max(until, Ordering::Relaxed);
+
+            if previous <= Utc::now().timestamp_millis() {
+                tracing::warn!(
+                    threshold = self.config.failure_threshold,
+                    window_secs = self.config.failure_window.as_secs(),
+                    "login failure rate exceeded, enabling strict per-account limits"
+                );
+            }
+        }
+    }
+}
+
+fn account_key(email: &str) -> String {
+    format!("login:account:{}", UserEmail::normalize(email))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::rate_limit::in_memory_store::InMemoryRateLimitStore;
+    use crate::shared::metrics::Metrics;
+
+    fn throttle(account_limit: u32) -> LoginThrottle {
+        LoginThrottle::new(
+            Arc::new(InMemoryRateLimitStore::new(64, 1, Arc::new(Metrics::new()))),
+            LoginThrottleConfig {
+                account_limit,
+                strict_account_limit: 1,
+                account_window: Duration::from_secs(60),
+                failure_threshold: 100,
+                failure_window: Duration::from_secs(60),
+                strict_cooldown: Duration::from_secs(60),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_use_up_the_account_quota() {
+        let throttle = throttle(3);
+
+        for _ in 0..3 {
+            throttle.check_account("user@example.com").await.unwrap();
+        }
+
+        assert!(throttle.check_account("USER@example.com").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn successful_logins_do_not_use_up_the_account_quota() {
+        let throttle = throttle(3);
+
+        for _ in 0..10 {
+            throttle.check_account("user@example.com").await.unwrap();
+            throttle.reset_account("user@example.com").await;
+        }
+    }
+}
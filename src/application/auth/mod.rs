@@ -1,2 +1,3 @@
+pub mod login_throttle;
 pub mod refresh_access_token;
 pub mod logout;
\ No newline at end of file
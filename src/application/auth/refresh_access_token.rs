@@ -9,7 +9,8 @@ use crate::domain::auth::refresh_token::RefreshToken;
 use crate::domain::auth::repository::{
     RefreshTokenRepository, RefreshTokenRepositoryError,
 };
-use crate::application::security::jwt::JwtService;
+use crate::application::security::jwt::{JwtService, TokenScope};
+use crate::domain::user::repository::UserRepository;
 
 #[derive(Debug, Error)]
 pub enum RefreshAccessTokenError {
@@ -22,24 +23,31 @@ pub enum RefreshAccessTokenError {
 pub struct RefreshResult {
     pub access_token: String,
     pub refresh_token: String,
+    pub scope: TokenScope,
 }
 
 pub struct RefreshAccessTokenUseCase {
     refresh_repo: Arc<dyn RefreshTokenRepository>,
+    user_repo: Arc<dyn UserRepository>,
     jwt_service: Arc<dyn JwtService>,
     refresh_ttl: i64,
+    password_max_age: Option<Duration>,
 }
 
 impl RefreshAccessTokenUseCase {
     pub fn new(
         refresh_repo: Arc<dyn RefreshTokenRepository>,
+        user_repo: Arc<dyn UserRepository>,
         jwt_service: Arc<dyn JwtService>,
         refresh_ttl: i64,
+        password_max_age: Option<Duration>,
     ) -> Self {
         Self {
             refresh_repo,
+            user_repo,
             jwt_service,
             refresh_ttl,
+            password_max_age,
         }
     }
 
@@ -62,6 +70,15 @@ impl RefreshAccessTokenUseCase {
 
         let user_id = token.user_id;
 
+        // The role and password state are re-read so that changes apply from
+        // the next access token onwards, and a restricted login cannot be
+        // refreshed into a full one.
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|_| RefreshAccessTokenError::Unexpected)?;
+
         self.refresh_repo
             .revoke(token.id)
             .await
@@ -84,14 +101,21 @@ impl RefreshAccessTokenUseCase {
             .await
             .map_err(|_| RefreshAccessTokenError::Unexpected)?;
 
+        let scope = if user.requires_password_change(self.password_max_age) {
+            TokenScope::PasswordChange
+        } else {
+            TokenScope::Full
+        };
+
         let access_token = self
             .jwt_service
-            .generate(user_id)
+            .generate(user_id, user.role(), scope)
             .map_err(|_| RefreshAccessTokenError::Unexpected)?;
 
         Ok(RefreshResult {
             access_token,
             refresh_token: new_value,
+            scope,
         })
     }
 }
@@ -1,4 +1,5 @@
 pub mod user;
 pub mod security;
 pub mod auth;
-pub mod audit;
\ No newline at end of file
+pub mod audit;
+pub mod rate_limit;
@@ -0,0 +1,171 @@
+use chrono::Utc;
+use serde_json::json;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::audit::audit_logger::AuditLogger;
+use crate::domain::audit::action::AuditAction;
+use crate::domain::rate_limit::algorithm::RateLimitAlgorithm;
+use crate::domain::rate_limit::ban::IpBan;
+use crate::domain::rate_limit::ban_store::BanStore;
+use crate::domain::rate_limit::store::RateLimitStore;
+
+#[derive(Debug, Clone)]
+pub struct AutoBanConfig {
+    /// Rate limit rejections within `window` that get an IP banned. Zero
+    /// disables auto-banning.
+    pub threshold: u32,
+    pub window: Duration,
+    pub duration: Duration,
+}
+
+/// Bans IPs that keep hitting rate limits. Strikes are counted in the rate
+/// limit store so they are shared between instances like the limits are.
+pub struct AutoBan {
+    strikes: Arc<dyn RateLimitStore>,
+    bans: Arc<dyn BanStore>,
+    audit: Arc<AuditLogger>,
+    config: AutoBanConfig,
+}
+
+impl AutoBan {
+    pub fn new(
+        strikes: Arc<dyn RateLimitStore>,
+        bans: Arc<dyn BanStore>,
+        audit: Arc<AuditLogger>,
+        config: AutoBanConfig,
+    ) -> Self {
+        Self {
+            strikes,
+            bans,
+            audit,
+            config,
+        }
+    }
+
+    /// Storage errors are treated as "not banned".
+    pub async fn find_ban(&self, ip: IpAddr) -> Option<IpBan> {
+        match self.bans.find(ip).await {
+            Ok(ban) => ban,
+            Err(_) => {
+                tracing::warn!(%ip, "ban store unavailable, skipping ban check");
+                None
+            }
+        }
+    }
+
+    pub async fn record_violation(&self, ip: IpAddr) {
+        if self.config.threshold == 0 {
+            return;
+        }
+
+        let decision = self
+            .strikes
+            .check(
+                format!("ban:strikes:{ip}"),
+                self.config.threshold,
+                self.config.window,
+                RateLimitAlgorithm::FixedWindow,
+            )
+            .await;
+
+        let Ok(decision) = decision else {
+            return;
+        };
+
+        // The strike that uses up the quota is the `threshold`th one.
+        if decision.allowed && decision.remaining > 0 {
+            return;
+        }
+
+        let now = Utc::now();
+        let ban = IpBan {
+            ip,
+            reason: "rate limit exceeded repeatedly".to_string(),
+            banned_at: now,
+            expires_at: now
+                + chrono::Duration::from_std(self.config.duration)
+                    .unwrap_or(chrono::Duration::zero()),
+        };
+
+        if self.bans.ban(ban.clone()).await.is_err() {
+            tracing::warn!(%ip, "failed to store automatic ban");
+            return;
+        }
+
+        tracing::warn!(%ip, expires_at = %ban.expires_at, "ip banned automatically");
+
+        self.audit
+            .log(
+                None,
+                AuditAction::IpBanned.as_str(),
+                "ip_ban",
+                json!({
+                    "ip": ip.to_string(),
+                    "reason": ban.reason,
+                    "expires_at": ban.expires_at,
+                }),
+            )
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test_support::RecordedAuditLogs;
+    use crate::infrastructure::rate_limit::in_memory_ban_store::InMemoryBanStore;
+    use crate::infrastructure::rate_limit::in_memory_store::InMemoryRateLimitStore;
+    use crate::shared::metrics::Metrics;
+
+    fn auto_ban(threshold: u32, duration: Duration) -> (AutoBan, Arc<RecordedAuditLogs>) {
+        let audit = Arc::new(RecordedAuditLogs::default());
+
+        let auto_ban = AutoBan::new(
+            Arc::new(InMemoryRateLimitStore::new(64, 1, Arc::new(Metrics::new()))),
+            Arc::new(InMemoryBanStore::new()),
+            Arc::new(AuditLogger::new(audit.clone())),
+            AutoBanConfig {
+                threshold,
+                window: Duration::from_secs(60),
+                duration,
+            },
+        );
+
+        (auto_ban, audit)
+    }
+
+    #[tokio::test]
+    async fn reaching_the_threshold_bans_until_expiry() {
+        let (auto_ban, audit) = auto_ban(3, Duration::from_millis(300));
+        let ip: IpAddr = "203.0.113.9".parse().unwrap();
+
+        for _ in 0..2 {
+            auto_ban.record_violation(ip).await;
+        }
+        assert!(auto_ban.find_ban(ip).await.is_none());
+
+        auto_ban.record_violation(ip).await;
+
+        let ban = auto_ban.find_ban(ip).await.expect("ip was not banned");
+        assert_eq!(ban.ip, ip);
+        assert!(ban.expires_at > Utc::now());
+        assert_eq!(audit.logs.lock().unwrap().len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(350)).await;
+        assert!(auto_ban.find_ban(ip).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn zero_threshold_never_bans() {
+        let (auto_ban, _) = auto_ban(0, Duration::from_secs(60));
+        let ip: IpAddr = "203.0.113.9".parse().unwrap();
+
+        for _ in 0..10 {
+            auto_ban.record_violation(ip).await;
+        }
+
+        assert!(auto_ban.find_ban(ip).await.is_none());
+    }
+}
@@ -0,0 +1,57 @@
+use serde_json::json;
+use std::net::IpAddr;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::application::audit::audit_logger::AuditLogger;
+use crate::domain::audit::action::AuditAction;
+use crate::domain::rate_limit::ban_store::BanStore;
+
+#[derive(Debug, Error)]
+pub enum LiftBanError {
+    #[error("ban not found")]
+    NotFound,
+
+    #[error("unexpected error")]
+    Unexpected,
+}
+
+pub struct LiftBanCommand {
+    pub actor_id: Uuid,
+    pub ip: IpAddr,
+}
+
+pub struct LiftBanUseCase {
+    bans: Arc<dyn BanStore>,
+    audit: Arc<AuditLogger>,
+}
+
+impl LiftBanUseCase {
+    pub fn new(bans: Arc<dyn BanStore>, audit: Arc<AuditLogger>) -> Self {
+        Self { bans, audit }
+    }
+
+    pub async fn execute(&self, cmd: LiftBanCommand) -> Result<(), LiftBanError> {
+        let lifted = self
+            .bans
+            .lift(cmd.ip)
+            .await
+            .map_err(|_| LiftBanError::Unexpected)?;
+
+        if !lifted {
+            return Err(LiftBanError::NotFound);
+        }
+
+        self.audit
+            .log(
+                Some(cmd.actor_id),
+                AuditAction::IpBanLifted.as_str(),
+                "ip_ban",
+                json!({ "ip": cmd.ip.to_string() }),
+            )
+            .await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::domain::rate_limit::{ban::IpBan, ban_store::BanStore};
+
+#[derive(Debug, Error)]
+pub enum ListBansError {
+    #[error("unexpected error")]
+    Unexpected,
+}
+
+pub struct ListBansUseCase {
+    bans: Arc<dyn BanStore>,
+}
+
+impl ListBansUseCase {
+    pub fn new(bans: Arc<dyn BanStore>) -> Self {
+        Self { bans }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<IpBan>, ListBansError> {
+        let mut bans = self.bans.list().await.map_err(|_| ListBansError::Unexpected)?;
+
+        bans.sort_by_key(|ban| std::cmp::Reverse(ban.banned_at));
+
+        Ok(bans)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod auto_ban;
+pub mod lift_ban;
+pub mod list_bans;
@@ -0,0 +1,5 @@
+/// Answers whether a password appears in a known breach corpus. Lookups
+/// are local; implementations must not call out to the network.
+pub trait BreachedPasswords: Send + Sync {
+    fn contains(&self, password: &str) -> bool;
+}
@@ -1,12 +1,45 @@
 use uuid::Uuid;
 
+use crate::domain::user::value_objects::UserRole;
+
+/// What an access token may be used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenScope {
+    Full,
+    /// Issued when the password must be changed before anything else.
+    PasswordChange,
+}
+
+impl TokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::Full => "full",
+            TokenScope::PasswordChange => "password_change",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "full" => Some(TokenScope::Full),
+            "password_change" => Some(TokenScope::PasswordChange),
+            _ => None,
+        }
+    }
+}
+
 pub struct JwtClaims {
     pub sub: Uuid,
-    pub exp: i64,
+    pub role: UserRole,
+    pub scope: TokenScope,
 }
 
 pub trait JwtService: Send + Sync {
-    fn generate(&self, user_id: Uuid) -> Result<String, JwtError>;
+    fn generate(
+        &self,
+        user_id: Uuid,
+        role: UserRole,
+        scope: TokenScope,
+    ) -> Result<String, JwtError>;
     fn verify(&self, token: &str) -> Result<JwtClaims, JwtError>;
 }
 
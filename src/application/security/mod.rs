@@ -1,3 +1,4 @@
+pub mod breached_passwords;
 pub mod password_hasher;
 pub mod jwt;
-pub mod password_policy;
\ No newline at end of file
+pub mod password_policy;
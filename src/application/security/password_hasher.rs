@@ -1,6 +1,18 @@
+use async_trait::async_trait;
+
+/// Hashing is CPU-bound; implementations must not run it on the async
+/// runtime's worker threads.
+#[async_trait]
 pub trait PasswordHasher: Send + Sync {
-    fn hash(&self, plain: &str) -> Result<String, PasswordHashError>;
-    fn verify(&self, plain: &str, hash: &str) -> Result<bool, PasswordHashError>;
+    async fn hash(&self, plain: &str) -> Result<String, PasswordHashError>;
+    async fn verify(&self, plain: &str, hash: &str) -> Result<bool, PasswordHashError>;
+
+    /// Whether `hash` was made with outdated parameters and should be
+    /// replaced the next time the plain password is known.
+    fn needs_rehash(&self, hash: &str) -> bool;
+
+    /// Whether `verify` understands the format of `hash`.
+    fn is_supported(&self, hash: &str) -> bool;
 }
 
 #[derive(Debug)]
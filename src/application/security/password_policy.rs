@@ -1,19 +1,369 @@
+use std::collections::HashSet;
+use std::sync::Arc;
 use thiserror::Error;
 
+use crate::application::security::breached_passwords::BreachedPasswords;
+use crate::domain::user::value_objects::UserEmail;
+
+/// List shipped with the binary, used when `PASSWORD_COMMON_LIST_PATH` is unset.
+const DEFAULT_COMMON_PASSWORDS: &str = include_str!("../../../config/common_passwords.txt");
+
+/// Keyboard rows and alphabets; runs along them add little entropy.
+const SEQUENCES: &[&str] = &[
+    "abcdefghijklmnopqrstuvwxyz",
+    "0123456789",
+    "qwertyuiop",
+    "asdfghjkl",
+    "zxcvbnm",
+];
+
 #[derive(Debug, Error)]
 pub enum PasswordPolicyError {
     #[error("password too weak")]
-    WeakPassword,
+    WeakPassword(Vec<PasswordViolation>),
 }
 
-pub struct PasswordPolicy;
+/// One failed rule. The message is shown to the user as is.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PasswordViolation {
+    #[error("password must be at least {0} characters")]
+    TooShort(usize),
+
+    #[error("password must be at most {0} characters")]
+    TooLong(usize),
+
+    #[error("password must contain a lowercase letter")]
+    MissingLowercase,
+
+    #[error("password must contain an uppercase letter")]
+    MissingUppercase,
+
+    #[error("password must contain a digit")]
+    MissingDigit,
+
+    #[error("password must contain a symbol")]
+    MissingSymbol,
+
+    #[error("password is too easy to guess")]
+    TooPredictable,
+
+    #[error("password must not contain your name or email")]
+    ContainsPersonalInfo,
+
+    #[error("password is too common")]
+    TooCommon,
+
+    #[error("password has appeared in a data breach")]
+    Breached,
+}
+
+#[derive(Debug, Clone)]
+pub struct PasswordPolicyConfig {
+    /// Lengths are counted in Unicode scalar values.
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Minimum estimated entropy; zero disables the check.
+    pub min_entropy_bits: f64,
+    pub common_list_path: Option<String>,
+}
+
+/// What is known about the account the password is for.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PasswordContext<'a> {
+    pub name: Option<&'a str>,
+    pub email: Option<&'a str>,
+}
+
+pub struct PasswordPolicy {
+    config: PasswordPolicyConfig,
+    common: HashSet<String>,
+    breached: Option<Arc<dyn BreachedPasswords>>,
+}
 
 impl PasswordPolicy {
-    pub fn validate(password: &str) -> Result<(), PasswordPolicyError> {
-        if password.len() < 8 {
-            return Err(PasswordPolicyError::WeakPassword);
+    pub fn load(config: PasswordPolicyConfig) -> std::io::Result<Self> {
+        let raw = match &config.common_list_path {
+            Some(path) => std::fs::read_to_string(path)?,
+            None => DEFAULT_COMMON_PASSWORDS.to_string(),
+        };
+
+        let common = raw
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+
+        Ok(Self {
+            config,
+            common,
+            breached: None,
+        })
+    }
+
+    pub fn with_breached_passwords(mut self, breached: Arc<dyn BreachedPasswords>) -> Self {
+        self.breached = Some(breached);
+        self
+    }
+
+    /// Collects every violation rather than stopping at the first.
+    pub fn validate(
+        &self,
+        password: &str,
+        context: PasswordContext<'_>,
+    ) -> Result<(), PasswordPolicyError> {
+        let mut violations = Vec::new();
+        let length = password.chars().count();
+
+        if length < self.config.min_length {
+            violations.push(PasswordViolation::TooShort(self.config.min_length));
+        }
+
+        if length > self.config.max_length {
+            violations.push(PasswordViolation::TooLong(self.config.max_length));
+        }
+
+        let classes = CharClasses::of(password);
+
+        if self.config.require_lowercase && !classes.lower {
+            violations.push(PasswordViolation::MissingLowercase);
+        }
+        if self.config.require_uppercase && !classes.upper {
+            violations.push(PasswordViolation::MissingUppercase);
+        }
+        if self.config.require_digit && !classes.digit {
+            violations.push(PasswordViolation::MissingDigit);
+        }
+        if self.config.require_symbol && !classes.symbol {
+            violations.push(PasswordViolation::MissingSymbol);
+        }
+
+        let lowered = password.to_lowercase();
+
+        if self.is_common(&lowered) {
+            violations.push(PasswordViolation::TooCommon);
+        }
+        if self
+            .breached
+            .as_ref()
+            .is_some_and(|breached| breached.contains(password))
+        {
+            violations.push(PasswordViolation::Breached);
+        }
+        if self.config.min_entropy_bits > 0.0
+            && estimate_entropy_bits(password) < self.config.min_entropy_bits
+        {
+            violations.push(PasswordViolation::TooPredictable);
+        }
+
+        if contains_personal_info(&lowered, context) {
+            violations.push(PasswordViolation::ContainsPersonalInfo);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(PasswordPolicyError::WeakPassword(violations))
+        }
+    }
+
+    /// Also catches a listed password with digits or symbols appended, such
+    /// as `password123!`.
+    fn is_common(&self, lowered: &str) -> bool {
+        let base = lowered.trim_end_matches(|c: char| !c.is_alphabetic());
+
+        self.common.contains(lowered) || (base.len() >= 4 && self.common.contains(base))
+    }
+}
+
+#[derive(Debug, Default)]
+struct CharClasses {
+    lower: bool,
+    upper: bool,
+    digit: bool,
+    symbol: bool,
+    other: bool,
+}
+
+impl CharClasses {
+    fn of(password: &str) -> Self {
+        let mut classes = Self::default();
+
+        for c in password.chars() {
+            if c.is_ascii_lowercase() {
+                classes.lower = true;
+            } else if c.is_ascii_uppercase() {
+                classes.upper = true;
+            } else if c.is_ascii_digit() {
+                classes.digit = true;
+            } else if c.is_ascii() {
+                classes.symbol = true;
+            } else if c.is_lowercase() {
+                classes.lower = true;
+                classes.other = true;
+            } else if c.is_uppercase() {
+                classes.upper = true;
+                classes.other = true;
+            } else {
+                classes.other = true;
+            }
+        }
+
+        classes
+    }
+
+    fn pool_size(&self) -> f64 {
+        [
+            (self.lower, 26.0),
+            (self.upper, 26.0),
+            (self.digit, 10.0),
+            (self.symbol, 33.0),
+            (self.other, 100.0),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, size)| size)
+        .sum()
+    }
+}
+
+/// Brute-force entropy over the character pool, with repeated characters
+/// and runs along keyboard rows or alphabets counted as a single guess each,
+/// the way zxcvbn scores repeat and sequence patterns.
+fn estimate_entropy_bits(password: &str) -> f64 {
+    let pool = CharClasses::of(password).pool_size();
+    if pool == 0.0 {
+        return 0.0;
+    }
+
+    let chars: Vec<char> = password.to_lowercase().chars().collect();
+    let mut effective = 0.0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let run = pattern_run(&chars[i..]);
+
+        // The first character is a full guess; the rest of a pattern only
+        // costs a little.
+        effective += 1.0 + (run - 1) as f64 * 0.25;
+        i += run;
+    }
+
+    effective * pool.log2()
+}
+
+/// Length of the repeat or sequence starting at `chars[0]`, at least 1.
+fn pattern_run(chars: &[char]) -> usize {
+    let repeat = chars.iter().take_while(|&&c| c == chars[0]).count();
+
+    let sequence = SEQUENCES
+        .iter()
+        .map(|seq| {
+            let seq: Vec<char> = seq.chars().collect();
+            let forward = run_along(chars, &seq);
+            let reversed: Vec<char> = seq.iter().rev().copied().collect();
+
+            forward.max(run_along(chars, &reversed))
+        })
+        .max()
+        .unwrap_or(1);
+
+    // Runs of two happen by chance and are not discounted.
+    let sequence = if sequence >= 3 { sequence } else { 1 };
+
+    repeat.max(sequence).max(1)
+}
+
+fn run_along(chars: &[char], seq: &[char]) -> usize {
+    let Some(start) = seq.iter().position(|&c| c == chars[0]) else {
+        return 1;
+    };
+
+    chars
+        .iter()
+        .zip(&seq[start..])
+        .take_while(|(a, b)| a == b)
+        .count()
+        .max(1)
+}
+
+fn contains_personal_info(lowered: &str, context: PasswordContext<'_>) -> bool {
+    let name_parts = context
+        .name
+        .into_iter()
+        .flat_map(str::split_whitespace)
+        .map(str::to_lowercase);
+
+    let email_local = context
+        .email
+        .map(UserEmail::normalize)
+        .and_then(|email| email.split('@').next().map(str::to_string));
+
+    name_parts
+        .chain(email_local)
+        .filter(|part| part.chars().count() >= 3)
+        .any(|part| lowered.contains(&part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Breached(&'static str);
+
+    impl BreachedPasswords for Breached {
+        fn contains(&self, password: &str) -> bool {
+            password == self.0
         }
+    }
+
+    fn policy(min_entropy_bits: f64) -> PasswordPolicy {
+        PasswordPolicy::load(PasswordPolicyConfig {
+            min_length: 12,
+            max_length: 128,
+            require_lowercase: false,
+            require_uppercase: true,
+            require_digit: false,
+            require_symbol: false,
+            min_entropy_bits,
+            common_list_path: None,
+        })
+        .unwrap()
+        .with_breached_passwords(Arc::new(Breached("password")))
+    }
+
+    fn violations(policy: &PasswordPolicy, password: &str) -> Vec<PasswordViolation> {
+        let context = PasswordContext {
+            name: Some("Ada"),
+            email: Some("ada@example.com"),
+        };
+
+        match policy.validate(password, context) {
+            Ok(()) => Vec::new(),
+            Err(PasswordPolicyError::WeakPassword(violations)) => violations,
+        }
+    }
+
+    #[test]
+    fn every_violation_is_reported() {
+        assert_eq!(
+            violations(&policy(40.0), "password"),
+            [
+                PasswordViolation::TooShort(12),
+                PasswordViolation::MissingUppercase,
+                PasswordViolation::TooCommon,
+                PasswordViolation::Breached,
+                PasswordViolation::TooPredictable,
+            ]
+        );
+    }
 
-        Ok(())
+    #[test]
+    fn strong_passwords_pass() {
+        assert!(violations(&policy(40.0), "Velvet-Harbor-Quietly-91").is_empty());
     }
 }
@@ -6,9 +6,13 @@ use uuid::Uuid;
 use crate::application::audit::audit_logger::AuditLogger;
 use crate::domain::audit::action::AuditAction;
 use crate::domain::auth::repository::RefreshTokenRepository;
+use crate::domain::user::password_history::PasswordHistoryRepository;
 use crate::http::extractors::client_context::ClientContext;
 use crate::{
-    application::security::{password_hasher::PasswordHasher, password_policy::PasswordPolicy},
+    application::security::{
+        password_hasher::PasswordHasher,
+        password_policy::{PasswordContext, PasswordPolicy, PasswordPolicyError, PasswordViolation},
+    },
     domain::user::repository::UserRepository,
 };
 
@@ -18,7 +22,10 @@ pub enum ChangePasswordError {
     InvalidCurrentPassword,
 
     #[error("weak password")]
-    WeakPassword,
+    WeakPassword(Vec<PasswordViolation>),
+
+    #[error("password was used recently")]
+    PasswordReused,
 
     #[error("unexpected error")]
     Unexpected,
@@ -34,22 +41,32 @@ pub struct ChangePasswordCommand {
 pub struct ChangePasswordUseCase {
     repo: Arc<dyn UserRepository>,
     refresh_repo: Arc<dyn RefreshTokenRepository>,
+    history_repo: Arc<dyn PasswordHistoryRepository>,
     hasher: Arc<dyn PasswordHasher>,
+    policy: Arc<PasswordPolicy>,
     audit: Arc<AuditLogger>,
+    /// Previous passwords, besides the current one, that may not be reused.
+    history_size: usize,
 }
 
 impl ChangePasswordUseCase {
     pub fn new(
         repo: Arc<dyn UserRepository>,
         refresh_repo: Arc<dyn RefreshTokenRepository>,
+        history_repo: Arc<dyn PasswordHistoryRepository>,
         hasher: Arc<dyn PasswordHasher>,
+        policy: Arc<PasswordPolicy>,
         audit: Arc<AuditLogger>,
+        history_size: usize,
     ) -> Self {
         Self {
             repo,
             refresh_repo,
+            history_repo,
             hasher,
+            policy,
             audit,
+            history_size,
         }
     }
 
@@ -63,6 +80,7 @@ impl ChangePasswordUseCase {
         let valid = self
             .hasher
             .verify(&cmd.current_password, user.password_hash())
+            .await
             .map_err(|_| ChangePasswordError::Unexpected)?;
 
         if !valid {
@@ -82,18 +100,50 @@ impl ChangePasswordUseCase {
             return Err(ChangePasswordError::InvalidCurrentPassword);
         }
 
-        PasswordPolicy::validate(&cmd.new_password)
-            .map_err(|_| ChangePasswordError::WeakPassword)?;
+        self.policy
+            .validate(
+                &cmd.new_password,
+                PasswordContext {
+                    name: Some(user.name().value()),
+                    email: Some(user.email().value()),
+                },
+            )
+            .map_err(|PasswordPolicyError::WeakPassword(violations)| {
+                ChangePasswordError::WeakPassword(violations)
+            })?;
+
+        if self.history_size > 0
+            && self
+                .is_reused(user.id(), &cmd.new_password, user.password_hash())
+                .await?
+        {
+            self.audit
+                .log(
+                    Some(user.id()),
+                    AuditAction::ChangePasswordFailed.as_str(),
+                    "user",
+                    json!({
+                        "ip": cmd.context.ip,
+                        "user_agent": cmd.context.user_agent,
+                        "reason": "password_reused",
+                    }),
+                )
+                .await;
+
+            return Err(ChangePasswordError::PasswordReused);
+        }
 
+        let previous_hash = user.password_hash().to_string();
         let new_hash = self
             .hasher
             .hash(&cmd.new_password)
+            .await
             .map_err(|_| ChangePasswordError::Unexpected)?;
 
         user.change_password(new_hash);
 
         self.repo
-            .update(&user)
+            .update_password(&user, &previous_hash, self.history_size)
             .await
             .map_err(|_| ChangePasswordError::Unexpected)?;
 
@@ -116,4 +166,37 @@ impl ChangePasswordUseCase {
 
         Ok(())
     }
+
+    /// Checks the current password first, then each remembered one; every
+    /// check is a full hash verification.
+    async fn is_reused(
+        &self,
+        user_id: Uuid,
+        new_password: &str,
+        current_hash: &str,
+    ) -> Result<bool, ChangePasswordError> {
+        let history = self
+            .history_repo
+            .recent(user_id, self.history_size)
+            .await
+            .map_err(|_| ChangePasswordError::Unexpected)?;
+
+        for hash in std::iter::once(current_hash).chain(history.iter().map(String::as_str)) {
+            if !self.hasher.is_supported(hash) {
+                continue;
+            }
+
+            let matches = self
+                .hasher
+                .verify(new_password, hash)
+                .await
+                .map_err(|_| ChangePasswordError::Unexpected)?;
+
+            if matches {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
 }
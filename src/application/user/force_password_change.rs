@@ -0,0 +1,64 @@
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::application::audit::audit_logger::AuditLogger;
+use crate::domain::audit::action::AuditAction;
+use crate::domain::user::repository::{UserRepository, UserRepositoryError};
+
+#[derive(Debug, Error)]
+pub enum ForcePasswordChangeError {
+    #[error("user not found")]
+    NotFound,
+
+    #[error("unexpected error")]
+    Unexpected,
+}
+
+pub struct ForcePasswordChangeCommand {
+    pub actor_id: Uuid,
+    pub user_id: Uuid,
+}
+
+/// Flags an account so that its next login or token refresh only yields a
+/// password-change token. Access tokens already issued stay valid until they
+/// expire.
+pub struct ForcePasswordChangeUseCase {
+    repo: Arc<dyn UserRepository>,
+    audit: Arc<AuditLogger>,
+}
+
+impl ForcePasswordChangeUseCase {
+    pub fn new(repo: Arc<dyn UserRepository>, audit: Arc<AuditLogger>) -> Self {
+        Self { repo, audit }
+    }
+
+    pub async fn execute(
+        &self,
+        cmd: ForcePasswordChangeCommand,
+    ) -> Result<(), ForcePasswordChangeError> {
+        let mut user = self.repo.find_by_id(cmd.user_id).await.map_err(|e| match e {
+            UserRepositoryError::NotFound => ForcePasswordChangeError::NotFound,
+            _ => ForcePasswordChangeError::Unexpected,
+        })?;
+
+        user.force_password_change();
+
+        self.repo
+            .update(&user)
+            .await
+            .map_err(|_| ForcePasswordChangeError::Unexpected)?;
+
+        self.audit
+            .log(
+                Some(cmd.actor_id),
+                AuditAction::PasswordChangeForced.as_str(),
+                "user",
+                json!({ "user_id": cmd.user_id }),
+            )
+            .await;
+
+        Ok(())
+    }
+}
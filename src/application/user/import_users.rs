@@ -0,0 +1,174 @@
+use chrono::Utc;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::application::audit::audit_logger::AuditLogger;
+use crate::application::security::password_hasher::PasswordHasher;
+use crate::domain::audit::action::AuditAction;
+use crate::domain::user::{
+    entity::User,
+    repository::{UserRepository, UserRepositoryError},
+    value_objects::{UserEmail, UserName, UserRole},
+};
+
+pub const MAX_IMPORT_BATCH: usize = 1000;
+
+#[derive(Debug, Error)]
+pub enum ImportUsersError {
+    #[error("too many users in one import")]
+    TooManyUsers,
+}
+
+pub struct ImportUserRecord {
+    pub name: String,
+    pub email: String,
+    /// Stored as is; Argon2 and the legacy formats are accepted.
+    pub password_hash: String,
+    pub role: Option<String>,
+}
+
+pub struct ImportUsersCommand {
+    pub actor_id: Uuid,
+    pub users: Vec<ImportUserRecord>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ImportFailureReason {
+    InvalidUserData,
+    UnsupportedHash,
+    EmailAlreadyExists,
+    Unexpected,
+}
+
+impl ImportFailureReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFailureReason::InvalidUserData => "invalid_user_data",
+            ImportFailureReason::UnsupportedHash => "unsupported_hash",
+            ImportFailureReason::EmailAlreadyExists => "email_already_exists",
+            ImportFailureReason::Unexpected => "unexpected",
+        }
+    }
+}
+
+pub struct ImportFailure {
+    /// Position of the record in the request.
+    pub index: usize,
+    pub email: String,
+    pub reason: ImportFailureReason,
+}
+
+pub struct ImportUsersResult {
+    pub imported: usize,
+    pub failures: Vec<ImportFailure>,
+}
+
+/// Creates users migrated from another system with their existing password
+/// hashes. Records are imported independently; one bad record does not fail
+/// the batch.
+pub struct ImportUsersUseCase {
+    repo: Arc<dyn UserRepository>,
+    hasher: Arc<dyn PasswordHasher>,
+    audit: Arc<AuditLogger>,
+}
+
+impl ImportUsersUseCase {
+    pub fn new(
+        repo: Arc<dyn UserRepository>,
+        hasher: Arc<dyn PasswordHasher>,
+        audit: Arc<AuditLogger>,
+    ) -> Self {
+        Self {
+            repo,
+            hasher,
+            audit,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        cmd: ImportUsersCommand,
+    ) -> Result<ImportUsersResult, ImportUsersError> {
+        if cmd.users.len() > MAX_IMPORT_BATCH {
+            return Err(ImportUsersError::TooManyUsers);
+        }
+
+        let mut imported = 0;
+        let mut failures = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (index, record) in cmd.users.into_iter().enumerate() {
+            let email = record.email.clone();
+
+            if !seen.insert(UserEmail::normalize(&email)) {
+                failures.push(ImportFailure {
+                    index,
+                    email,
+                    reason: ImportFailureReason::EmailAlreadyExists,
+                });
+                continue;
+            }
+
+            match self.import(record).await {
+                Ok(()) => imported += 1,
+                Err(reason) => failures.push(ImportFailure {
+                    index,
+                    email,
+                    reason,
+                }),
+            }
+        }
+
+        self.audit
+            .log(
+                Some(cmd.actor_id),
+                AuditAction::UsersImported.as_str(),
+                "user",
+                json!({
+                    "imported": imported,
+                    "failed": failures.len(),
+                }),
+            )
+            .await;
+
+        Ok(ImportUsersResult { imported, failures })
+    }
+
+    async fn import(&self, record: ImportUserRecord) -> Result<(), ImportFailureReason> {
+        let name = UserName::new(record.name).map_err(|_| ImportFailureReason::InvalidUserData)?;
+        let email = UserEmail::new(record.email).map_err(|_| ImportFailureReason::InvalidUserData)?;
+
+        let role = match record.role.as_deref() {
+            Some(role) => UserRole::parse(role).map_err(|_| ImportFailureReason::InvalidUserData)?,
+            None => UserRole::User,
+        };
+
+        if !self.hasher.is_supported(&record.password_hash) {
+            return Err(ImportFailureReason::UnsupportedHash);
+        }
+
+        match self.repo.find_by_email(email.value()).await {
+            Ok(_) => return Err(ImportFailureReason::EmailAlreadyExists),
+            Err(UserRepositoryError::NotFound) => {}
+            Err(_) => return Err(ImportFailureReason::Unexpected),
+        }
+
+        let user = User::restore(
+            Uuid::now_v7(),
+            name,
+            email,
+            record.password_hash,
+            role,
+            Utc::now(),
+            false,
+        );
+
+        self.repo.save(&user).await.map_err(|e| match e {
+            UserRepositoryError::Conflict => ImportFailureReason::EmailAlreadyExists,
+            _ => ImportFailureReason::Unexpected,
+        })
+    }
+}
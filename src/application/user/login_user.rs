@@ -1,9 +1,13 @@
 use crate::application::audit::audit_logger::AuditLogger;
+use crate::application::auth::login_throttle::{LoginThrottle, LoginThrottleError};
+use crate::application::security::jwt::TokenScope;
 use crate::application::security::password_hasher::PasswordHasher;
 use crate::domain::audit::action::AuditAction;
 use crate::domain::auth::refresh_token::RefreshToken;
 use crate::domain::auth::repository::RefreshTokenRepository;
+use crate::domain::user::entity::User;
 use crate::domain::user::repository::{UserRepository, UserRepositoryError};
+use crate::domain::user::value_objects::UserRole;
 use base64::{engine::general_purpose, Engine as _};
 use chrono::{Duration, Utc};
 use rand::{rng, RngCore};
@@ -18,6 +22,9 @@ pub enum LoginUserError {
     #[error("invalid credentials")]
     InvalidCredentials,
 
+    #[error("too many login attempts")]
+    TooManyAttempts,
+
     #[error("unexpected error")]
     Unexpected,
 }
@@ -30,7 +37,11 @@ pub struct LoginUserCommand {
 
 pub struct LoginResult {
     pub user_id: Uuid,
+    pub role: UserRole,
     pub refresh_token: String,
+    /// `PasswordChange` when the password expired or an admin requires a
+    /// new one.
+    pub scope: TokenScope,
 }
 
 pub struct LoginUserUseCase {
@@ -38,7 +49,9 @@ pub struct LoginUserUseCase {
     refresh_token_repo: Arc<dyn RefreshTokenRepository>,
     hasher: Arc<dyn PasswordHasher>,
     audit: Arc<AuditLogger>,
+    throttle: Arc<LoginThrottle>,
     refresh_ttl: i64,
+    password_max_age: Option<Duration>,
 }
 
 impl LoginUserUseCase {
@@ -47,30 +60,60 @@ impl LoginUserUseCase {
         refresh_token_repo: Arc<dyn RefreshTokenRepository>,
         hasher: Arc<dyn PasswordHasher>,
         audit: Arc<AuditLogger>,
+        throttle: Arc<LoginThrottle>,
         refresh_ttl: i64,
+        password_max_age: Option<Duration>,
     ) -> Self {
         Self {
             user_repo,
             refresh_token_repo,
             hasher,
             audit,
+            throttle,
             refresh_ttl,
+            password_max_age,
         }
     }
 
     pub async fn execute(&self, cmd: LoginUserCommand) -> Result<LoginResult, LoginUserError> {
-        let user = match self.user_repo.find_by_email(&cmd.email).await {
+        if let Err(LoginThrottleError::Throttled { retry_after }) =
+            self.throttle.check_account(&cmd.email).await
+        {
+            self.audit
+                .log(
+                    None,
+                    AuditAction::LoginThrottled.as_str(),
+                    "auth",
+                    json!({
+                        "ip": cmd.context.ip,
+                        "user_agent": cmd.context.user_agent,
+                        "strict": self.throttle.is_strict(),
+                        "retry_after_secs": retry_after.map(|d| d.as_secs()),
+                    }),
+                )
+                .await;
+
+            return Err(LoginUserError::TooManyAttempts);
+        }
+
+        let mut user = match self.user_repo.find_by_email(&cmd.email).await {
             Ok(user) => user,
-            Err(UserRepositoryError::NotFound) => return Err(LoginUserError::InvalidCredentials),
+            Err(UserRepositoryError::NotFound) => {
+                self.throttle.record_failure().await;
+                return Err(LoginUserError::InvalidCredentials);
+            }
             Err(_) => return Err(LoginUserError::Unexpected),
         };
 
         let verified = self
             .hasher
             .verify(&cmd.password, user.password_hash())
+            .await
             .map_err(|_| LoginUserError::Unexpected)?;
 
         if !verified {
+            self.throttle.record_failure().await;
+
             self.audit
                 .log(
                     None,
@@ -87,6 +130,12 @@ impl LoginUserUseCase {
             return Err(LoginUserError::InvalidCredentials);
         }
 
+        self.throttle.reset_account(&cmd.email).await;
+
+        if self.hasher.needs_rehash(user.password_hash()) {
+            self.rehash(&mut user, &cmd.password).await;
+        }
+
         let mut buf = [0u8; 32];
         rng().fill_bytes(&mut buf);
         let refresh_token_value = general_purpose::STANDARD.encode(buf);
@@ -116,9 +165,31 @@ impl LoginUserUseCase {
             )
             .await;
 
+        let scope = if user.requires_password_change(self.password_max_age) {
+            TokenScope::PasswordChange
+        } else {
+            TokenScope::Full
+        };
+
         Ok(LoginResult {
             user_id: user.id(),
+            role: user.role(),
             refresh_token: refresh_token_value,
+            scope,
         })
     }
+
+    /// Best effort: on failure the old hash stays and the login proceeds.
+    async fn rehash(&self, user: &mut User, password: &str) {
+        let Ok(hash) = self.hasher.hash(password).await else {
+            tracing::warn!(user_id = %user.id(), "failed to rehash password");
+            return;
+        };
+
+        user.rehash_password(hash);
+
+        if self.user_repo.update(user).await.is_err() {
+            tracing::warn!(user_id = %user.id(), "failed to store rehashed password");
+        }
+    }
 }
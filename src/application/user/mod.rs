@@ -2,4 +2,6 @@ pub mod register_user;
 pub mod login_user;
 pub mod get_current_user;
 pub mod update_profile;
-pub mod change_password;
\ No newline at end of file
+pub mod change_password;
+pub mod import_users;
+pub mod force_password_change;
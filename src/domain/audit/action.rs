@@ -2,8 +2,13 @@
 pub enum AuditAction {
     LoginSuccess,
     LoginFailed,
+    LoginThrottled,
     ChangePasswordSuccess,
     ChangePasswordFailed,
+    IpBanned,
+    IpBanLifted,
+    UsersImported,
+    PasswordChangeForced,
 }
 
 impl AuditAction {
@@ -11,8 +16,13 @@ impl AuditAction {
         match self {
             AuditAction::LoginSuccess => "LOGIN_SUCCESS",
             AuditAction::LoginFailed => "LOGIN_FAILED",
+            AuditAction::LoginThrottled => "LOGIN_THROTTLED",
             AuditAction::ChangePasswordSuccess => "CHANGE_PASSWORD_SUCCESS",
             AuditAction::ChangePasswordFailed => "CHANGE_PASSWORD_FAILED",
+            AuditAction::IpBanned => "IP_BANNED",
+            AuditAction::IpBanLifted => "IP_BAN_LIFTED",
+            AuditAction::UsersImported => "USERS_IMPORTED",
+            AuditAction::PasswordChangeForced => "PASSWORD_CHANGE_FORCED",
         }
     }
 }
@@ -1,3 +1,4 @@
 pub mod entity;
 pub mod repository;
-pub mod action;
\ No newline at end of file
+pub mod action;
+pub mod filter;
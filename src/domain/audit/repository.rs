@@ -1,8 +1,34 @@
 use async_trait::async_trait;
+use futures_util::stream::BoxStream;
 
-use super::entity::AuditLog;
+use super::{entity::AuditLog, filter::AuditLogFilter};
+
+#[derive(Debug)]
+pub enum AuditLogRepositoryError {
+    Unexpected,
+}
+
+pub type AuditLogStream = BoxStream<'static, Result<AuditLog, AuditLogRepositoryError>>;
 
 #[async_trait]
 pub trait AuditLogRepository: Send + Sync {
     async fn store(&self, log: AuditLog);
+
+    async fn find(
+        &self,
+        filter: &AuditLogFilter,
+        limit: u32,
+        offset: u64,
+    ) -> Result<Vec<AuditLog>, AuditLogRepositoryError>;
+
+    async fn count(&self, filter: &AuditLogFilter) -> Result<u64, AuditLogRepositoryError>;
+
+    /// Distinct top-level `metadata` keys across the rows matched by `filter`.
+    async fn metadata_keys(
+        &self,
+        filter: &AuditLogFilter,
+    ) -> Result<Vec<String>, AuditLogRepositoryError>;
+
+    /// Rows matched by `filter` in `created_at` order, fetched lazily.
+    fn stream(&self, filter: AuditLogFilter) -> AuditLogStream;
 }
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitAlgorithm {
+    /// Counter reset at the end of each window. Cheap, but allows up to
+    /// `2 * limit` requests around a window boundary.
+    #[default]
+    FixedWindow,
+    /// Weighted blend of the previous and current window counters.
+    SlidingWindow,
+    /// Bucket of `limit` tokens refilled evenly over `window`.
+    TokenBucket,
+}
+
+impl RateLimitAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitAlgorithm::FixedWindow => "fixed_window",
+            RateLimitAlgorithm::SlidingWindow => "sliding_window",
+            RateLimitAlgorithm::TokenBucket => "token_bucket",
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use std::net::IpAddr;
+
+use super::ban::IpBan;
+
+#[derive(Debug)]
+pub enum BanStoreError {
+    StorageError,
+}
+
+/// Temporary IP bans. Expired bans are never returned.
+#[async_trait]
+pub trait BanStore: Send + Sync {
+    async fn ban(&self, ban: IpBan) -> Result<(), BanStoreError>;
+
+    async fn find(&self, ip: IpAddr) -> Result<Option<IpBan>, BanStoreError>;
+
+    async fn list(&self) -> Result<Vec<IpBan>, BanStoreError>;
+
+    /// Returns whether an active ban was removed.
+    async fn lift(&self, ip: IpAddr) -> Result<bool, BanStoreError>;
+}
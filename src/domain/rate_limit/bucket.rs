@@ -1,5 +1,7 @@
 use std::time::{Duration, Instant};
 
+use super::decision::RateLimitDecision;
+
 pub struct RateLimitBucket {
     pub count: u32,
     pub reset_at: Instant,
@@ -13,7 +15,7 @@ impl RateLimitBucket {
         }
     }
 
-    pub fn allow(&mut self, limit: u32, window: Duration) -> bool {
+    pub fn allow(&mut self, limit: u32, window: Duration) -> RateLimitDecision {
         let now = Instant::now();
 
         if now >= self.reset_at {
@@ -21,7 +23,16 @@ impl RateLimitBucket {
             self.reset_at = now + window;
         }
 
-        self.count += 1;
-        self.count <= limit
+        self.count = self.count.saturating_add(1);
+
+        let reset_after = self.reset_at - now;
+
+        RateLimitDecision::new(
+            self.count <= limit,
+            limit,
+            limit.saturating_sub(self.count),
+            reset_after,
+            reset_after,
+        )
     }
 }
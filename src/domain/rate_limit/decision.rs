@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Time until the quota is fully restored.
+    pub reset_after: Duration,
+    /// Time until the next request would be allowed. Only set once the quota
+    /// is exhausted.
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitDecision {
+    pub fn new(
+        allowed: bool,
+        limit: u32,
+        remaining: u32,
+        reset_after: Duration,
+        retry_after: Duration,
+    ) -> Self {
+        Self {
+            allowed,
+            limit,
+            remaining,
+            reset_after,
+            retry_after: (!allowed || remaining == 0).then_some(retry_after),
+        }
+    }
+}
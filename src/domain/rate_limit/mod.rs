@@ -1,2 +1,8 @@
+pub mod algorithm;
+pub mod ban;
+pub mod ban_store;
 pub mod bucket;
-pub mod store;
\ No newline at end of file
+pub mod decision;
+pub mod sliding_window;
+pub mod store;
+pub mod token_bucket;
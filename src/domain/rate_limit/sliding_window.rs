@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+use super::decision::RateLimitDecision;
+
+pub struct SlidingWindowCounter {
+    pub window_start: Instant,
+    pub current: u32,
+    pub previous: u32,
+}
+
+impl SlidingWindowCounter {
+    pub fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            current: 0,
+            previous: 0,
+        }
+    }
+
+    pub fn allow(&mut self, limit: u32, window: Duration) -> RateLimitDecision {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.window_start);
+
+        if elapsed >= window {
+            let windows_passed = (elapsed.as_nanos() / window.as_nanos()) as u32;
+
+            self.previous = if windows_passed == 1 { self.current } else { 0 };
+            self.current = 0;
+            self.window_start += window * windows_passed;
+        }
+
+        let window_secs = window.as_secs_f64();
+        let into_window = now.duration_since(self.window_start).as_secs_f64();
+        let left_in_window = window_secs - into_window;
+        let previous_weight = 1.0 - into_window / window_secs;
+        let estimated = f64::from(self.previous) * previous_weight + f64::from(self.current);
+
+        let allowed = estimated + 1.0 <= f64::from(limit);
+        let estimated = if allowed {
+            self.current += 1;
+            estimated + 1.0
+        } else {
+            estimated
+        };
+
+        // The previous window's weight decays linearly, so the next slot opens
+        // once enough of it has slid out. A full current window becomes the
+        // previous one at the boundary and keeps weighing on the next window.
+        let spare = f64::from(limit) - 1.0 - f64::from(self.current);
+        let retry_after = if spare >= 0.0 && self.previous > 0 {
+            (left_in_window - spare * window_secs / f64::from(self.previous)).max(0.0)
+        } else if spare < 0.0 && self.current > 0 {
+            left_in_window + window_secs * (1.0 - (f64::from(limit) - 1.0) / f64::from(self.current))
+        } else {
+            left_in_window
+        };
+
+        // Requests counted in this window keep weighing on the next one.
+        let reset_after = if self.current > 0 {
+            left_in_window + window_secs
+        } else {
+            left_in_window
+        };
+
+        RateLimitDecision::new(
+            allowed,
+            limit,
+            (f64::from(limit) - estimated).max(0.0) as u32,
+            Duration::from_secs_f64(reset_after),
+            Duration::from_secs_f64(retry_after),
+        )
+    }
+}
@@ -1,6 +1,8 @@
 use async_trait::async_trait;
 use std::time::Duration;
 
+use super::{algorithm::RateLimitAlgorithm, decision::RateLimitDecision};
+
 #[derive(Debug)]
 pub enum RateLimitError {
     StorageError,
@@ -13,5 +15,9 @@ pub trait RateLimitStore: Send + Sync {
         key: String,
         limit: u32,
         window_secs: Duration,
-    ) -> Result<bool, RateLimitError>;
+        algorithm: RateLimitAlgorithm,
+    ) -> Result<RateLimitDecision, RateLimitError>;
+
+    /// Forgets everything counted against `key`, restoring its full quota.
+    async fn reset(&self, key: String, algorithm: RateLimitAlgorithm) -> Result<(), RateLimitError>;
 }
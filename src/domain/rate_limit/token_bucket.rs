@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+use super::decision::RateLimitDecision;
+
+pub struct TokenBucket {
+    pub tokens: f64,
+    pub last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            tokens: f64::from(limit),
+            last_refill: Instant::now(),
+        }
+    }
+
+    pub fn allow(&mut self, limit: u32, window: Duration) -> RateLimitDecision {
+        let now = Instant::now();
+        let capacity = f64::from(limit);
+        let refill_per_sec = capacity / window.as_secs_f64();
+
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_sec).min(capacity);
+        self.last_refill = now;
+
+        let allowed = self.tokens >= 1.0;
+        if allowed {
+            self.tokens -= 1.0;
+        }
+
+        RateLimitDecision::new(
+            allowed,
+            limit,
+            self.tokens.floor() as u32,
+            Duration::from_secs_f64((capacity - self.tokens) / refill_per_sec),
+            Duration::from_secs_f64((1.0 - self.tokens).max(0.0) / refill_per_sec),
+        )
+    }
+}
@@ -1,6 +1,7 @@
+use chrono::{DateTime, Duration, Utc};
 use uuid::Uuid;
 
-use crate::domain::user::value_objects::{UserEmail, UserName};
+use crate::domain::user::value_objects::{UserEmail, UserName, UserRole};
 
 #[derive(Debug, Clone)]
 pub struct User {
@@ -8,6 +9,9 @@ pub struct User {
     name: UserName,
     email: UserEmail,
     password_hash: String,
+    role: UserRole,
+    password_changed_at: DateTime<Utc>,
+    must_change_password: bool,
 }
 
 impl User {
@@ -22,6 +26,29 @@ impl User {
             name,
             email,
             password_hash,
+            role: UserRole::User,
+            password_changed_at: Utc::now(),
+            must_change_password: false,
+        }
+    }
+
+    pub fn restore(
+        id: Uuid,
+        name: UserName,
+        email: UserEmail,
+        password_hash: String,
+        role: UserRole,
+        password_changed_at: DateTime<Utc>,
+        must_change_password: bool,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            email,
+            password_hash,
+            role,
+            password_changed_at,
+            must_change_password,
         }
     }
 
@@ -41,11 +68,46 @@ impl User {
         &self.password_hash
     }
 
+    pub fn role(&self) -> UserRole {
+        self.role
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+
+    pub fn password_changed_at(&self) -> DateTime<Utc> {
+        self.password_changed_at
+    }
+
+    pub fn must_change_password(&self) -> bool {
+        self.must_change_password
+    }
+
+    /// True when an admin flagged the account or the password is older than
+    /// `max_age`.
+    pub fn requires_password_change(&self, max_age: Option<Duration>) -> bool {
+        self.must_change_password
+            || max_age.is_some_and(|max_age| Utc::now() - self.password_changed_at > max_age)
+    }
+
+    pub fn force_password_change(&mut self) {
+        self.must_change_password = true;
+    }
+
     pub fn rename(&mut self, name: UserName) {
         self.name = name;
     }
 
     pub fn change_password(&mut self, new_password_hash: String) {
         self.password_hash = new_password_hash;
+        self.password_changed_at = Utc::now();
+        self.must_change_password = false;
+    }
+
+    /// Replaces the hash of the unchanged password, e.g. with stronger
+    /// hashing parameters.
+    pub fn rehash_password(&mut self, password_hash: String) {
+        self.password_hash = password_hash;
     }
 }
@@ -7,4 +7,7 @@ pub enum UserDomainError {
 
     #[error("user name is too short")]
     InvalidName,
+
+    #[error("user role is unknown")]
+    UnknownRole,
 }
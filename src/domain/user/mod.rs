@@ -1,4 +1,5 @@
 pub mod entity;
 pub mod value_objects;
 pub mod errors;
+pub mod password_history;
 pub mod repository;
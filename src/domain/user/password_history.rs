@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum PasswordHistoryRepositoryError {
+    Unexpected,
+}
+
+/// Hashes of passwords a user has replaced, newest first.
+#[async_trait]
+pub trait PasswordHistoryRepository: Send + Sync {
+    async fn recent(
+        &self,
+        user_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<String>, PasswordHistoryRepositoryError>;
+}
@@ -20,4 +20,13 @@ pub trait UserRepository: Send + Sync {
     async fn save(&self, user: &User) -> Result<(), UserRepositoryError>;
     
     async fn update(&self, user: &User) -> Result<(), UserRepositoryError>;
+
+    /// Saves a changed password and remembers the hash it replaced, keeping
+    /// the newest `keep_history` entries, in one transaction.
+    async fn update_password(
+        &self,
+        user: &User,
+        previous_hash: &str,
+        keep_history: usize,
+    ) -> Result<(), UserRepositoryError>;
 }
@@ -15,6 +15,11 @@ impl UserEmail {
     pub fn value(&self) -> &str {
         &self.0
     }
+
+    /// Canonical form used when an email is a lookup or throttling key.
+    pub fn normalize(raw: &str) -> String {
+        raw.trim().to_lowercase()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,3 +38,26 @@ impl UserName {
         &self.0
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UserRole {
+    User,
+    Admin,
+}
+
+impl UserRole {
+    pub fn parse(value: &str) -> Result<Self, UserDomainError> {
+        match value {
+            "user" => Ok(UserRole::User),
+            "admin" => Ok(UserRole::Admin),
+            _ => Err(UserDomainError::UnknownRole),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::User => "user",
+            UserRole::Admin => "admin",
+        }
+    }
+}
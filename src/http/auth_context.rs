@@ -1,6 +1,11 @@
 use uuid::Uuid;
 
+use crate::application::security::jwt::TokenScope;
+use crate::domain::user::value_objects::UserRole;
+
 #[derive(Clone, Debug)]
 pub struct AuthContext {
     pub user_id: Uuid,
+    pub role: UserRole,
+    pub scope: TokenScope,
 }
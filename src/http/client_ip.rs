@@ -0,0 +1,215 @@
+use axum::http::HeaderMap;
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+
+/// The forwarding header the trusted proxies set, selected by
+/// `TRUSTED_PROXY_HEADER`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ForwardedHeader {
+    /// RFC 7239 `Forwarded: for=...`.
+    Forwarded,
+    #[default]
+    XForwardedFor,
+}
+
+impl ForwardedHeader {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "forwarded" => Some(Self::Forwarded),
+            "x-forwarded-for" => Some(Self::XForwardedFor),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves the originating client address of a request.
+///
+/// Forwarding headers are only honoured when the TCP peer is a trusted proxy,
+/// and only the one header those proxies set is read: a proxy that appends to
+/// `X-Forwarded-For` passes a client-supplied `Forwarded` through untouched.
+/// The chain is walked from the right, skipping trusted hops, so a client
+/// cannot spoof its address by prepending entries.
+#[derive(Debug, Clone, Default)]
+pub struct ClientIpResolver {
+    trusted_proxies: Vec<IpNet>,
+    header: ForwardedHeader,
+}
+
+impl ClientIpResolver {
+    pub fn new(trusted_proxies: Vec<IpNet>, header: ForwardedHeader) -> Self {
+        Self {
+            trusted_proxies,
+            header,
+        }
+    }
+
+    pub fn resolve(&self, headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<IpAddr> {
+        let peer = peer?.ip().to_canonical();
+
+        if !self.is_trusted(peer) {
+            return Some(peer);
+        }
+
+        let chain = match self.header {
+            ForwardedHeader::Forwarded => forwarded_chain(headers),
+            ForwardedHeader::XForwardedFor => x_forwarded_for_chain(headers),
+        };
+        let Some(chain) = chain else {
+            return Some(peer);
+        };
+
+        let mut client = peer;
+
+        for hop in chain.iter().rev() {
+            // An unparseable hop (`unknown`, an obfuscated identifier) ends the
+            // trusted part of the chain; the last known address is used.
+            let Some(ip) = hop else {
+                break;
+            };
+
+            client = *ip;
+
+            if !self.is_trusted(client) {
+                break;
+            }
+        }
+
+        Some(client)
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.trusted_proxies.iter().any(|net| net.contains(&ip))
+    }
+}
+
+/// `for=` values of the RFC 7239 `Forwarded` header, in order.
+fn forwarded_chain(headers: &HeaderMap) -> Option<Vec<Option<IpAddr>>> {
+    let mut chain = Vec::new();
+
+    for value in headers.get_all("forwarded") {
+        let value = value.to_str().ok()?;
+
+        for element in value.split(',') {
+            let node = element.split(';').find_map(|pair| {
+                let (name, value) = pair.trim().split_once('=')?;
+                name.trim().eq_ignore_ascii_case("for").then_some(value.trim())
+            });
+
+            if let Some(node) = node {
+                chain.push(parse_node(node));
+            }
+        }
+    }
+
+    (!chain.is_empty()).then_some(chain)
+}
+
+fn x_forwarded_for_chain(headers: &HeaderMap) -> Option<Vec<Option<IpAddr>>> {
+    let mut chain = Vec::new();
+
+    for value in headers.get_all("x-forwarded-for") {
+        let value = value.to_str().ok()?;
+        chain.extend(value.split(',').map(|hop| parse_node(hop.trim())));
+    }
+
+    (!chain.is_empty()).then_some(chain)
+}
+
+/// Accepts `1.2.3.4`, `1.2.3.4:80`, `2001:db8::1` and `"[2001:db8::1]:80"`.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim_matches('"');
+
+    if let Ok(ip) = node.parse::<IpAddr>() {
+        return Some(ip.to_canonical());
+    }
+
+    if let Ok(addr) = node.parse::<SocketAddr>() {
+        return Some(addr.ip().to_canonical());
+    }
+
+    node.strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(ip, _)| ip.parse::<IpAddr>().ok())
+        .map(|ip| ip.to_canonical())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    const PROXY: &str = "10.0.0.1:443";
+
+    fn resolver(header: ForwardedHeader) -> ClientIpResolver {
+        ClientIpResolver::new(vec!["10.0.0.0/8".parse().unwrap()], header)
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn untrusted_peer_ignores_forwarding_headers() {
+        let headers = headers(&[("x-forwarded-for", "1.2.3.4")]);
+
+        let resolved = resolver(ForwardedHeader::XForwardedFor)
+            .resolve(&headers, Some("203.0.113.9:1234".parse().unwrap()));
+
+        assert_eq!(resolved, ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn x_forwarded_for_is_walked_from_the_right() {
+        let headers = headers(&[("x-forwarded-for", "1.2.3.4, 198.51.100.7, 10.0.0.2")]);
+
+        let resolved =
+            resolver(ForwardedHeader::XForwardedFor).resolve(&headers, Some(PROXY.parse().unwrap()));
+
+        assert_eq!(resolved, ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn client_supplied_forwarded_is_ignored_behind_an_x_forwarded_for_proxy() {
+        let headers = headers(&[
+            ("forwarded", "for=1.2.3.4"),
+            ("x-forwarded-for", "198.51.100.7"),
+        ]);
+
+        let resolved =
+            resolver(ForwardedHeader::XForwardedFor).resolve(&headers, Some(PROXY.parse().unwrap()));
+
+        assert_eq!(resolved, ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn client_supplied_x_forwarded_for_is_ignored_behind_a_forwarded_proxy() {
+        let headers = headers(&[
+            ("x-forwarded-for", "1.2.3.4"),
+            ("forwarded", "for=\"[2001:db8::1]:80\""),
+        ]);
+
+        let resolved =
+            resolver(ForwardedHeader::Forwarded).resolve(&headers, Some(PROXY.parse().unwrap()));
+
+        assert_eq!(resolved, ip("2001:db8::1"));
+    }
+
+    #[test]
+    fn missing_header_falls_back_to_the_peer() {
+        let headers = headers(&[("forwarded", "for=1.2.3.4")]);
+
+        let resolved =
+            resolver(ForwardedHeader::XForwardedFor).resolve(&headers, Some(PROXY.parse().unwrap()));
+
+        assert_eq!(resolved, ip("10.0.0.1"));
+    }
+}
@@ -0,0 +1,252 @@
+use axum::http::Method;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// A request that could not get a slot and was shed.
+#[derive(Debug)]
+pub struct Overloaded;
+
+/// A capped pool of in-flight slots with a bounded wait queue. Requests
+/// beyond `max_in_flight` wait up to `queue_timeout`, and only while fewer
+/// than `max_queued` others are waiting.
+pub struct Bulkhead {
+    slots: Arc<Semaphore>,
+    max_queued: usize,
+    queued: AtomicUsize,
+    queue_timeout: Duration,
+}
+
+impl Bulkhead {
+    pub fn new(max_in_flight: usize, max_queued: usize, queue_timeout: Duration) -> Self {
+        Self {
+            slots: Arc::new(Semaphore::new(max_in_flight.max(1))),
+            max_queued,
+            queued: AtomicUsize::new(0),
+            queue_timeout,
+        }
+    }
+
+    pub async fn acquire(&self) -> Result<OwnedSemaphorePermit, Overloaded> {
+        if let Ok(permit) = self.slots.clone().try_acquire_owned() {
+            return Ok(permit);
+        }
+
+        let position = self.queued.fetch_add(1, Ordering::AcqRel);
+        let _queued = QueuePosition(&self.queued);
+
+        if position >= self.max_queued {
+            return Err(Overloaded);
+        }
+
+        let permit = tokio::time::timeout(self.queue_timeout, self.slots.clone().acquire_owned()).await;
+
+        match permit {
+            Ok(Ok(permit)) => Ok(permit),
+            _ => Err(Overloaded),
+        }
+    }
+
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+}
+
+/// Gives the queue position back however the wait ends, including when the
+/// waiting request is dropped because its client went away.
+struct QueuePosition<'a>(&'a AtomicUsize);
+
+impl Drop for QueuePosition<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteConcurrencyLimit {
+    pub method: Method,
+    /// Route template as registered in the router.
+    pub route: String,
+    pub max_in_flight: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConcurrencyConfig {
+    pub max_in_flight: usize,
+    pub max_queued: usize,
+    pub queue_timeout: Duration,
+    /// Sent as `Retry-After` on shed requests.
+    pub retry_after: Duration,
+    pub routes: Vec<RouteConcurrencyLimit>,
+}
+
+/// Caps concurrent requests globally and, for expensive routes such as those
+/// hashing passwords, per route.
+pub struct ConcurrencyLimiter {
+    global: Bulkhead,
+    routes: HashMap<(Method, String), Bulkhead>,
+    retry_after: Duration,
+}
+
+/// Held for the lifetime of a request.
+pub struct ConcurrencyPermit {
+    _route: Option<OwnedSemaphorePermit>,
+    _global: OwnedSemaphorePermit,
+}
+
+impl ConcurrencyLimiter {
+    pub fn new(config: &ConcurrencyConfig) -> Self {
+        let routes = config
+            .routes
+            .iter()
+            .map(|limit| {
+                (
+                    (limit.method.clone(), limit.route.clone()),
+                    Bulkhead::new(limit.max_in_flight, config.max_queued, config.queue_timeout),
+                )
+            })
+            .collect();
+
+        Self {
+            global: Bulkhead::new(config.max_in_flight, config.max_queued, config.queue_timeout),
+            routes,
+            retry_after: config.retry_after,
+        }
+    }
+
+    /// The route slot is taken first so that requests queued on a busy route
+    /// do not hold global slots other routes could use.
+    pub async fn acquire(&self, method: &Method, route: &str) -> Result<ConcurrencyPermit, Overloaded> {
+        let route = match self.routes.get(&(method.clone(), route.to_string())) {
+            Some(bulkhead) => Some(bulkhead.acquire().await?),
+            None => None,
+        };
+
+        let global = self.global.acquire().await?;
+
+        Ok(ConcurrencyPermit {
+            _route: route,
+            _global: global,
+        })
+    }
+
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+
+    pub fn queued(&self) -> usize {
+        self.global.queued() + self.routes.values().map(Bulkhead::queued).sum::<usize>()
+    }
+
+    pub fn route_limits(&self) -> impl Iterator<Item = &(Method, String)> {
+        self.routes.keys()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    const LONG: Duration = Duration::from_secs(10);
+
+    /// Yields until `bulkhead` has `count` waiters.
+    async fn until_queued(bulkhead: &Bulkhead, count: usize) {
+        while bulkhead.queued() != count {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn full_queue_sheds_at_once() {
+        let bulkhead = Arc::new(Bulkhead::new(1, 1, LONG));
+        let _busy = bulkhead.acquire().await.unwrap();
+
+        let waiter = tokio::spawn({
+            let bulkhead = bulkhead.clone();
+            async move { bulkhead.acquire().await.map(drop) }
+        });
+        until_queued(&bulkhead, 1).await;
+
+        let started = Instant::now();
+        assert!(bulkhead.acquire().await.is_err());
+        assert!(started.elapsed() < LONG);
+        assert_eq!(bulkhead.queued(), 1);
+
+        waiter.abort();
+    }
+
+    #[tokio::test]
+    async fn waiting_past_the_queue_timeout_sheds() {
+        let bulkhead = Bulkhead::new(1, 4, Duration::from_millis(50));
+        let _busy = bulkhead.acquire().await.unwrap();
+
+        assert!(bulkhead.acquire().await.is_err());
+        assert_eq!(bulkhead.queued(), 0);
+    }
+
+    #[tokio::test]
+    async fn cancelled_waiters_give_back_their_queue_position() {
+        let bulkhead = Arc::new(Bulkhead::new(1, 1, LONG));
+        let busy = bulkhead.acquire().await.unwrap();
+
+        let waiter = tokio::spawn({
+            let bulkhead = bulkhead.clone();
+            async move { bulkhead.acquire().await.map(drop) }
+        });
+        until_queued(&bulkhead, 1).await;
+
+        // A client disconnecting drops its request future mid-wait.
+        waiter.abort();
+        let _ = waiter.await;
+        assert_eq!(bulkhead.queued(), 0);
+
+        // The freed position can be used again.
+        let next = tokio::spawn({
+            let bulkhead = bulkhead.clone();
+            async move { bulkhead.acquire().await.map(drop) }
+        });
+        until_queued(&bulkhead, 1).await;
+        drop(busy);
+
+        assert!(next.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn requests_queued_on_a_route_do_not_hold_global_slots() {
+        let limiter = Arc::new(ConcurrencyLimiter::new(&ConcurrencyConfig {
+            max_in_flight: 2,
+            max_queued: 4,
+            queue_timeout: LONG,
+            retry_after: Duration::from_secs(1),
+            routes: vec![RouteConcurrencyLimit {
+                method: Method::POST,
+                route: "/auth/login".into(),
+                max_in_flight: 1,
+            }],
+        }));
+
+        let _login = limiter.acquire(&Method::POST, "/auth/login").await.unwrap();
+
+        let queued_login = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire(&Method::POST, "/auth/login").await.map(drop) }
+        });
+        while limiter.queued() != 1 {
+            tokio::task::yield_now().await;
+        }
+
+        // The waiting login holds no global slot, so this gets the second
+        // one straight away.
+        let other = tokio::time::timeout(
+            Duration::from_millis(100),
+            limiter.acquire(&Method::GET, "/users/me"),
+        )
+        .await;
+        assert!(matches!(other, Ok(Ok(_))));
+
+        queued_login.abort();
+    }
+}
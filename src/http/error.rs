@@ -1,10 +1,18 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use std::collections::HashMap;
 
-use crate::shared::error::ApiErrorResponse;
+use crate::application::security::password_policy::PasswordViolation;
+use crate::http::problem::{error_context, type_uri, ProblemDetails, PROBLEM_JSON};
+use crate::http::validation::ValidationErrors;
+use crate::shared::error::{ApiErrorResponse, ErrorDetails};
+use crate::shared::request_id;
+
+/// Messages per request field.
+pub type FieldErrors = HashMap<String, Vec<String>>;
 
 #[derive(Debug)]
 pub enum ApiError {
@@ -27,7 +35,7 @@ pub enum ApiError {
     Validation {
         code: &'static str,
         message: &'static str,
-        errors: std::collections::HashMap<String, Vec<String>>,
+        errors: FieldErrors,
     },
     Internal {
         code: &'static str,
@@ -36,57 +44,103 @@ pub enum ApiError {
     TooManyRequests {
         code: &'static str,
         message: &'static str,
+    },
+    ServiceUnavailable {
+        code: &'static str,
+        message: &'static str,
+    },
+    UnsupportedMediaType {
+        code: &'static str,
+        message: &'static str,
+    },
+    MethodNotAllowed {
+        code: &'static str,
+        message: &'static str,
+    },
+}
+
+impl ApiError {
+    /// Every violation becomes its own message under `field`.
+    pub fn weak_password(field: &str, violations: &[PasswordViolation]) -> Self {
+        let mut errors = ValidationErrors::new();
+        errors.weak_password(field, violations);
+
+        errors.into()
     }
 }
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
+impl ApiError {
+    fn parts(self) -> (StatusCode, &'static str, &'static str, Option<FieldErrors>) {
         match self {
-            ApiError::BadRequest { code, message } => {
-                let body = ApiErrorResponse::simple(code, message);
-                (StatusCode::BAD_REQUEST, Json(body)).into_response()
+            ApiError::BadRequest { code, message } => (StatusCode::BAD_REQUEST, code, message, None),
+            ApiError::Unauthorized { code, message } => (StatusCode::UNAUTHORIZED, code, message, None),
+            ApiError::Forbidden { code, message } => (StatusCode::FORBIDDEN, code, message, None),
+            ApiError::NotFound { code, message } => (StatusCode::NOT_FOUND, code, message, None),
+            ApiError::Validation {
+                code,
+                message,
+                errors,
+            } => (StatusCode::UNPROCESSABLE_ENTITY, code, message, Some(errors)),
+            ApiError::Internal { code, message } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, code, message, None)
             }
-
-            ApiError::Unauthorized { code, message } => {
-                let body = ApiErrorResponse::simple(code, message);
-                (StatusCode::UNAUTHORIZED, Json(body)).into_response()
+            ApiError::TooManyRequests { code, message } => {
+                (StatusCode::TOO_MANY_REQUESTS, code, message, None)
             }
-
-            ApiError::Forbidden { code, message } => {
-                let body = ApiErrorResponse::simple(code, message);
-                (StatusCode::FORBIDDEN, Json(body)).into_response()
+            ApiError::ServiceUnavailable { code, message } => {
+                (StatusCode::SERVICE_UNAVAILABLE, code, message, None)
             }
-
-            ApiError::NotFound { code, message } => {
-                let body = ApiErrorResponse::simple(code, message);
-                (StatusCode::NOT_FOUND, Json(body)).into_response()
+            ApiError::UnsupportedMediaType { code, message } => {
+                (StatusCode::UNSUPPORTED_MEDIA_TYPE, code, message, None)
+            }
+            ApiError::MethodNotAllowed { code, message } => {
+                (StatusCode::METHOD_NOT_ALLOWED, code, message, None)
             }
+        }
+    }
+}
 
-            ApiError::Validation {
-                code,
-                message,
+/// Renders the `ApiErrorResponse` envelope, or problem details when the
+/// request asked for `application/problem+json`.
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let context = error_context();
+        let (status, code, message, errors) = self.parts();
+
+        let mut response = if context.problem_json {
+            let body = ProblemDetails {
+                type_uri: type_uri(&context.type_base, code),
+                title: message.to_string(),
+                status: status.as_u16(),
+                code: code.to_string(),
+                instance: request_id::current().map(|id| id.to_string()),
                 errors,
-            } => {
-                let body = ApiErrorResponse::new(
+            };
+
+            let mut response = (status, Json(body)).into_response();
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+            response
+        } else {
+            let body = match errors {
+                Some(errors) => ApiErrorResponse::new(
                     code,
                     message,
-                    Some(crate::shared::error::ErrorDetails {
+                    Some(ErrorDetails {
                         fields: Some(errors),
                     }),
-                );
+                ),
+                None => ApiErrorResponse::simple(code, message),
+            };
 
-                (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response()
-            }
+            (status, Json(body)).into_response()
+        };
 
-            ApiError::Internal { code, message } => {
-                let body = ApiErrorResponse::simple(code, message);
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(body)).into_response()
-            }
+        response
+            .headers_mut()
+            .insert(header::VARY, HeaderValue::from_static("accept"));
 
-            ApiError::TooManyRequests { code, message } => {
-                let body = ApiErrorResponse::simple(code, message);
-                (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response()
-            }
-        }
+        response
     }
 }
@@ -0,0 +1,8 @@
+use axum::extract::{FromRequestParts, Path};
+
+use crate::http::error::ApiError;
+
+/// `Path<T>` with its rejection turned into an `ApiError`.
+#[derive(FromRequestParts)]
+#[from_request(via(Path), rejection(ApiError))]
+pub struct ApiPath<T>(pub T);
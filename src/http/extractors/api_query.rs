@@ -0,0 +1,8 @@
+use axum::extract::{FromRequestParts, Query};
+
+use crate::http::error::ApiError;
+
+/// `Query<T>` with its rejection turned into an `ApiError`.
+#[derive(FromRequestParts)]
+#[from_request(via(Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
@@ -1,9 +1,11 @@
 use axum::{
-    extract::{ConnectInfo, FromRequestParts},
+    extract::{ConnectInfo, FromRef, FromRequestParts},
     http::request::Parts,
 };
 use std::net::SocketAddr;
 
+use crate::shared::state::AppState;
+
 #[derive(Debug, Clone)]
 pub struct ClientContext {
     pub ip: Option<String>,
@@ -13,22 +15,23 @@ pub struct ClientContext {
 impl<S> FromRequestParts<S> for ClientContext
 where
     S: Send + Sync,
+    AppState: FromRef<S>,
 {
     type Rejection = ();
 
     fn from_request_parts(
         parts: &mut Parts,
-        _state: &S,
+        state: &S,
     ) -> impl Future<Output = Result<Self, <Self as FromRequestParts<S>>::Rejection>> + Send {
+        let state = AppState::from_ref(state);
         let headers = parts.headers.clone();
         let connect_info = parts.extensions.get::<ConnectInfo<SocketAddr>>().cloned();
 
         Box::pin(async move {
-            let ip = headers
-                .get("x-forwarded-for")
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.split(',').next().unwrap_or(s).to_string())
-                .or_else(|| connect_info.map(|ci| ci.0.ip().to_string()));
+            let ip = state
+                .client_ip_resolver
+                .resolve(&headers, connect_info.map(|ci| ci.0))
+                .map(|ip| ip.to_string());
 
             let user_agent = headers
                 .get("user-agent")
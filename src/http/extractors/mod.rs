@@ -1,2 +1,5 @@
+pub mod api_path;
+pub mod api_query;
 pub mod auth_user;
-pub mod client_context;
\ No newline at end of file
+pub mod client_context;
+pub mod validated_json;
@@ -0,0 +1,147 @@
+use axum::body::Bytes;
+use axum::extract::{FromRequest, Request};
+use axum::http::{header, HeaderMap};
+use serde::de::DeserializeOwned;
+use serde_json::error::Category;
+
+use crate::http::error::ApiError;
+use crate::http::validation::Validate;
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+/// `Json<T>` that also runs `T::validate`, and reports body problems in the
+/// usual error format: wrong content type as 415, broken JSON as 400, and
+/// missing or mistyped fields as 422 under the field's path.
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> FromRequest<AppState> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        if !is_json(req.headers()) {
+            return Err(ApiError::UnsupportedMediaType {
+                code: api_codes::validator::UNSUPPORTED_MEDIA_TYPE,
+                message: api_messages::validator::UNSUPPORTED_MEDIA_TYPE,
+            });
+        }
+
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|_| ApiError::BadRequest {
+                code: api_codes::validator::INVALID_REQUEST_BODY,
+                message: api_messages::validator::INVALID_REQUEST_BODY,
+            })?;
+
+        let value: T = deserialize(&bytes)?;
+
+        value.validate(state)?;
+
+        Ok(Self(value))
+    }
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+
+    mime.eq_ignore_ascii_case("application/json")
+        || (mime.starts_with("application/") && mime.ends_with("+json"))
+}
+
+fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, ApiError> {
+    let malformed = || ApiError::BadRequest {
+        code: api_codes::validator::MALFORMED_JSON,
+        message: api_messages::validator::MALFORMED_JSON,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+        let path = err.path().to_string();
+        let inner = err.into_inner();
+
+        match inner.classify() {
+            Category::Data => {
+                // Missing fields are reported against the enclosing object,
+                // so the field name is appended to its path.
+                let field = match missing_field(&inner) {
+                    Some(field) if path == "." => field,
+                    Some(field) => format!("{path}.{field}"),
+                    None => path,
+                };
+                let message = inner.to_string();
+                let message = message
+                    .rsplit_once(" at line ")
+                    .map_or(message.as_str(), |(message, _)| message);
+
+                ApiError::Validation {
+                    code: api_codes::validator::VALIDATION_ERROR,
+                    message: api_messages::validator::INVALID_FIELDS,
+                    errors: [(field, vec![message.to_string()])].into_iter().collect(),
+                }
+            }
+            _ => malformed(),
+        }
+    })?;
+
+    deserializer.end().map_err(|_| malformed())?;
+
+    Ok(value)
+}
+
+fn missing_field(error: &serde_json::Error) -> Option<String> {
+    let message = error.to_string();
+    let rest = message.strip_prefix("missing field `")?;
+
+    rest.split_once('`').map(|(field, _)| field.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Import {
+        users: Vec<Row>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Row {
+        name: String,
+        email: String,
+    }
+
+    fn failing_field(body: &str) -> String {
+        match deserialize::<Import>(body.as_bytes()) {
+            Err(ApiError::Validation { errors, .. }) => errors.into_keys().next().unwrap(),
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn missing_fields_keep_their_full_path() {
+        let body = r#"{"users": [{"name": "a", "email": "a@x.io"}, {"name": "b"}]}"#;
+
+        assert_eq!(failing_field(body), "users[1].email");
+        assert_eq!(failing_field("{}"), "users");
+    }
+
+    #[test]
+    fn mistyped_fields_keep_their_full_path() {
+        let body = r#"{"users": [{"name": 1, "email": "a@x.io"}]}"#;
+
+        assert_eq!(failing_field(body), "users[0].name");
+    }
+}
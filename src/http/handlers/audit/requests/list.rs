@@ -0,0 +1,65 @@
+use axum::extract::State;
+use axum::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::application::audit::list_audit_logs::{ListAuditLogsQuery, ListAuditLogsUseCase};
+use crate::http::error::ApiError;
+use crate::shared::error::ApiErrorResponse;
+use crate::http::extractors::api_query::ApiQuery;
+use crate::http::handlers::audit::requests::query::AuditLogQuery;
+use crate::http::handlers::audit::responses::audit_log_response::AuditLogResponse;
+use crate::shared::response::{ApiResponse, Meta, PaginationMeta};
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/audit-logs",
+    tag = "admin",
+    security(("bearer_auth" = [])),
+    params(AuditLogQuery, PageQuery),
+    responses(
+        (status = 200, description = "Page of audit logs", body = ApiResponse<Vec<AuditLogResponse>>),
+        (status = 403, description = "Not an admin", body = ApiErrorResponse),
+    )
+)]
+pub async fn list_audit_logs(
+    State(state): State<AppState>,
+    ApiQuery(filter): ApiQuery<AuditLogQuery>,
+    ApiQuery(paging): ApiQuery<PageQuery>,
+) -> Result<Json<ApiResponse<Vec<AuditLogResponse>>>, ApiError> {
+    let use_case = ListAuditLogsUseCase::new(state.audit_log_repo.clone());
+
+    let result = use_case
+        .execute(ListAuditLogsQuery {
+            filter: filter.into(),
+            page: paging.page.unwrap_or(1),
+            per_page: paging.per_page.unwrap_or(20),
+        })
+        .await
+        .map_err(|_| ApiError::Internal {
+            code: api_codes::audit::LIST_AUDIT_LOGS_FAILED,
+            message: api_messages::audit::LIST_AUDIT_LOGS_FAILED,
+        })?;
+
+    Ok(Json(ApiResponse::success_with_meta(
+        api_codes::audit::LIST_AUDIT_LOGS_SUCCESS,
+        api_messages::audit::LIST_AUDIT_LOGS_SUCCESS,
+        result.items.into_iter().map(Into::into).collect(),
+        Meta {
+            pagination: Some(PaginationMeta {
+                page: result.page,
+                per_page: result.per_page,
+                total: result.total,
+            }),
+        },
+    )))
+}
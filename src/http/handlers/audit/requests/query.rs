@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::domain::audit::filter::AuditLogFilter;
+
+/// Filters shared by the audit listing and export endpoints.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditLogQuery {
+    pub actor_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub resource: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl From<AuditLogQuery> for AuditLogFilter {
+    fn from(query: AuditLogQuery) -> Self {
+        Self {
+            actor_id: query.actor_id,
+            action: query.action,
+            resource: query.resource,
+            from: query.from,
+            to: query.to,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use axum::extract::State;
+use axum::Json;
+use std::net::IpAddr;
+
+use crate::application::rate_limit::lift_ban::{LiftBanCommand, LiftBanError, LiftBanUseCase};
+use crate::http::error::ApiError;
+use crate::shared::error::ApiErrorResponse;
+use crate::http::extractors::api_path::ApiPath;
+use crate::http::extractors::auth_user::AuthUser;
+use crate::shared::response::ApiResponse;
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+#[utoipa::path(
+    delete,
+    path = "/bans/{ip}",
+    tag = "admin",
+    security(("bearer_auth" = [])),
+    params(("ip" = String, Path, description = "Banned IP address")),
+    responses(
+        (status = 200, description = "Ban lifted", body = ApiResponse<serde_json::Value>),
+        (status = 400, description = "Invalid IP address", body = ApiErrorResponse),
+        (status = 404, description = "No active ban", body = ApiErrorResponse),
+    )
+)]
+pub async fn lift_ban(
+    State(state): State<AppState>,
+    AuthUser(auth): AuthUser,
+    ApiPath(ip): ApiPath<String>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    let ip: IpAddr = ip.parse().map_err(|_| ApiError::BadRequest {
+        code: api_codes::bans::INVALID_IP_ADDRESS,
+        message: api_messages::bans::INVALID_IP_ADDRESS,
+    })?;
+
+    let use_case = LiftBanUseCase::new(state.ban_store.clone(), state.audit_logger.clone());
+
+    use_case
+        .execute(LiftBanCommand {
+            actor_id: auth.user_id,
+            ip: ip.to_canonical(),
+        })
+        .await
+        .map_err(|e| match e {
+            LiftBanError::NotFound => ApiError::NotFound {
+                code: api_codes::bans::BAN_NOT_FOUND,
+                message: api_messages::bans::BAN_NOT_FOUND,
+            },
+            LiftBanError::Unexpected => ApiError::Internal {
+                code: api_codes::bans::LIFT_BAN_FAILED,
+                message: api_messages::bans::LIFT_BAN_FAILED,
+            },
+        })?;
+
+    Ok(Json(ApiResponse::empty_success(
+        api_codes::bans::LIFT_BAN_SUCCESS,
+        api_messages::bans::LIFT_BAN_SUCCESS,
+    )))
+}
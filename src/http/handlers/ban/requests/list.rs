@@ -0,0 +1,37 @@
+use axum::extract::State;
+use axum::Json;
+
+use crate::application::rate_limit::list_bans::ListBansUseCase;
+use crate::http::error::ApiError;
+use crate::shared::error::ApiErrorResponse;
+use crate::http::handlers::ban::responses::ban_response::BanResponse;
+use crate::shared::response::ApiResponse;
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+#[utoipa::path(
+    get,
+    path = "/bans",
+    tag = "admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Active bans", body = ApiResponse<Vec<BanResponse>>),
+        (status = 403, description = "Not an admin", body = ApiErrorResponse),
+    )
+)]
+pub async fn list_bans(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<BanResponse>>>, ApiError> {
+    let use_case = ListBansUseCase::new(state.ban_store.clone());
+
+    let bans = use_case.execute().await.map_err(|_| ApiError::Internal {
+        code: api_codes::bans::LIST_BANS_FAILED,
+        message: api_messages::bans::LIST_BANS_FAILED,
+    })?;
+
+    Ok(Json(ApiResponse::success(
+        api_codes::bans::LIST_BANS_SUCCESS,
+        api_messages::bans::LIST_BANS_SUCCESS,
+        bans.into_iter().map(Into::into).collect(),
+    )))
+}
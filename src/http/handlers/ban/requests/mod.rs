@@ -0,0 +1,2 @@
+pub mod lift;
+pub mod list;
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::rate_limit::ban::IpBan;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BanResponse {
+    pub ip: String,
+    pub reason: String,
+    pub banned_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<IpBan> for BanResponse {
+    fn from(ban: IpBan) -> Self {
+        Self {
+            ip: ban.ip.to_string(),
+            reason: ban.reason,
+            banned_at: ban.banned_at,
+            expires_at: ban.expires_at,
+        }
+    }
+}
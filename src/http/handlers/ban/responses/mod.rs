@@ -0,0 +1 @@
+pub mod ban_response;
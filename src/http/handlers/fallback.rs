@@ -0,0 +1,17 @@
+use crate::http::error::ApiError;
+use crate::shared::{api_codes, api_messages};
+
+pub async fn not_found() -> ApiError {
+    ApiError::NotFound {
+        code: api_codes::server::ROUTE_NOT_FOUND,
+        message: api_messages::server::ROUTE_NOT_FOUND,
+    }
+}
+
+/// axum still adds the `Allow` header to this response.
+pub async fn method_not_allowed() -> ApiError {
+    ApiError::MethodNotAllowed {
+        code: api_codes::server::METHOD_NOT_ALLOWED,
+        message: api_messages::server::METHOD_NOT_ALLOWED,
+    }
+}
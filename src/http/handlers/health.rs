@@ -2,6 +2,12 @@ use axum::Json;
 use crate::shared::{api_codes, api_messages};
 use crate::shared::response::ApiResponse;
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "system",
+    responses((status = 200, description = "Service is up", body = ApiResponse<serde_json::Value>))
+)]
 pub async fn health_check() -> Json<ApiResponse<()>> {
     Json(ApiResponse::empty_success(
         api_codes::health::HEALTH_OK,
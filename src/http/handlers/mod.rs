@@ -1,2 +1,6 @@
+pub mod audit;
+pub mod ban;
+pub mod fallback;
 pub mod health;
-pub mod user;
\ No newline at end of file
+pub mod metrics;
+pub mod user;
@@ -1,5 +1,6 @@
 use axum::{extract::State, Json};
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::{
     application::user::change_password::{
@@ -10,25 +11,56 @@ use crate::{
 };
 use crate::http::extractors::auth_user::AuthUser;
 use crate::http::extractors::client_context::ClientContext;
+use crate::http::extractors::validated_json::ValidatedJson;
+use crate::http::validation::{Validate, ValidationErrors};
 use crate::shared::{api_codes, api_messages};
+use crate::shared::error::ApiErrorResponse;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ChangePasswordRequest {
     pub current_password: String,
     pub new_password: String,
 }
 
+/// The password policy needs the account's name and email, so it runs in
+/// the use case rather than here.
+impl Validate for ChangePasswordRequest {
+    fn validate(&self, _state: &AppState) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.require("current_password", &self.current_password);
+        errors.require("new_password", &self.new_password);
+
+        errors.into_result()
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/me/change-password",
+    tag = "users",
+    security(("bearer_auth" = [])),
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "Wrong current password", body = ApiErrorResponse),
+        (status = 422, description = "Weak or reused password", body = ApiErrorResponse),
+    )
+)]
 pub async fn change_password(
     State(state): State<AppState>,
     client_ctx: ClientContext,
     AuthUser(auth): AuthUser,
-    Json(payload): Json<ChangePasswordRequest>,
+    ValidatedJson(payload): ValidatedJson<ChangePasswordRequest>,
 ) -> Result<Json<ApiResponse<()>>, ApiError> {
     let use_case = ChangePasswordUseCase::new(
         state.user_repo.clone(),
         state.refresh_token_repo.clone(),
+        state.password_history_repo.clone(),
         state.password_hasher.clone(),
+        state.password_policy.clone(),
         state.audit_logger.clone(),
+        state.config.password_history_size,
     );
 
 
@@ -48,10 +80,16 @@ pub async fn change_password(
                 code: api_codes::validator::VALIDATION_ERROR,
                 message: api_messages::validator::INVALID_CURRENT_PASSWORD,
             },
-            ChangePasswordError::WeakPassword => ApiError::Validation {
-                code: api_codes::users::WEAK_PASSWORD,
-                message: api_messages::users::WEAK_PASSWORD,
-                errors: Default::default(),
+            ChangePasswordError::WeakPassword(violations) => ApiError::weak_password("new_password", &violations),
+            ChangePasswordError::PasswordReused => ApiError::Validation {
+                code: api_codes::users::PASSWORD_REUSED,
+                message: api_messages::users::PASSWORD_REUSED,
+                errors: [(
+                    "new_password".to_string(),
+                    vec![api_messages::users::PASSWORD_REUSED.to_string()],
+                )]
+                .into_iter()
+                .collect(),
             },
             _ => ApiError::Internal {
                 code: api_codes::users::CHANGE_PASSWORD_FAILED,
@@ -0,0 +1,62 @@
+use axum::extract::State;
+use axum::Json;
+use uuid::Uuid;
+
+use crate::application::user::force_password_change::{
+    ForcePasswordChangeCommand, ForcePasswordChangeError, ForcePasswordChangeUseCase,
+};
+use crate::http::error::ApiError;
+use crate::shared::error::ApiErrorResponse;
+use crate::http::extractors::api_path::ApiPath;
+use crate::http::extractors::auth_user::AuthUser;
+use crate::shared::response::ApiResponse;
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/force-password-change",
+    tag = "admin",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Password change required at next login", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "Not an admin", body = ApiErrorResponse),
+        (status = 404, description = "User not found", body = ApiErrorResponse),
+    )
+)]
+pub async fn force_password_change(
+    State(state): State<AppState>,
+    AuthUser(auth): AuthUser,
+    ApiPath(user_id): ApiPath<String>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| ApiError::BadRequest {
+        code: api_codes::users::INVALID_USER_ID,
+        message: api_messages::users::INVALID_USER_ID,
+    })?;
+
+    let use_case =
+        ForcePasswordChangeUseCase::new(state.user_repo.clone(), state.audit_logger.clone());
+
+    use_case
+        .execute(ForcePasswordChangeCommand {
+            actor_id: auth.user_id,
+            user_id,
+        })
+        .await
+        .map_err(|e| match e {
+            ForcePasswordChangeError::NotFound => ApiError::NotFound {
+                code: api_codes::users::USER_NOT_FOUND,
+                message: api_messages::users::USER_NOT_FOUND,
+            },
+            ForcePasswordChangeError::Unexpected => ApiError::Internal {
+                code: api_codes::users::FORCE_PASSWORD_CHANGE_FAILED,
+                message: api_messages::users::FORCE_PASSWORD_CHANGE_FAILED,
+            },
+        })?;
+
+    Ok(Json(ApiResponse::empty_success(
+        api_codes::users::FORCE_PASSWORD_CHANGE_SUCCESS,
+        api_messages::users::FORCE_PASSWORD_CHANGE_SUCCESS,
+    )))
+}
@@ -0,0 +1,90 @@
+use axum::extract::State;
+use axum::Json;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::application::user::import_users::{
+    ImportUserRecord, ImportUsersCommand, ImportUsersError, ImportUsersUseCase,
+};
+use crate::http::error::ApiError;
+use crate::shared::error::ApiErrorResponse;
+use crate::http::extractors::auth_user::AuthUser;
+use crate::http::extractors::validated_json::ValidatedJson;
+use crate::http::validation::{Validate, ValidationErrors};
+use crate::http::handlers::user::responses::import_users_response::ImportUsersResponse;
+use crate::shared::response::ApiResponse;
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportUserRequest {
+    pub name: String,
+    pub email: String,
+    pub password_hash: String,
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportUsersRequest {
+    pub users: Vec<ImportUserRequest>,
+}
+
+/// Records are checked one by one by the use case, which reports failures
+/// per record instead of rejecting the whole batch.
+impl Validate for ImportUsersRequest {
+    fn validate(&self, _state: &AppState) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/import",
+    tag = "admin",
+    security(("bearer_auth" = [])),
+    request_body = ImportUsersRequest,
+    responses(
+        (status = 200, description = "Import report", body = ApiResponse<ImportUsersResponse>),
+        (status = 400, description = "Batch too large", body = ApiErrorResponse),
+        (status = 403, description = "Not an admin", body = ApiErrorResponse),
+    )
+)]
+pub async fn import_users(
+    State(state): State<AppState>,
+    AuthUser(auth): AuthUser,
+    ValidatedJson(payload): ValidatedJson<ImportUsersRequest>,
+) -> Result<Json<ApiResponse<ImportUsersResponse>>, ApiError> {
+    let use_case = ImportUsersUseCase::new(
+        state.user_repo.clone(),
+        state.password_hasher.clone(),
+        state.audit_logger.clone(),
+    );
+
+    let result = use_case
+        .execute(ImportUsersCommand {
+            actor_id: auth.user_id,
+            users: payload
+                .users
+                .into_iter()
+                .map(|u| ImportUserRecord {
+                    name: u.name,
+                    email: u.email,
+                    password_hash: u.password_hash,
+                    role: u.role,
+                })
+                .collect(),
+        })
+        .await
+        .map_err(|e| match e {
+            ImportUsersError::TooManyUsers => ApiError::BadRequest {
+                code: api_codes::users::IMPORT_TOO_LARGE,
+                message: api_messages::users::IMPORT_TOO_LARGE,
+            },
+        })?;
+
+    Ok(Json(ApiResponse::success(
+        api_codes::users::IMPORT_USERS_SUCCESS,
+        api_messages::users::IMPORT_USERS_SUCCESS,
+        result.into(),
+    )))
+}
@@ -1,38 +1,69 @@
 use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
+use crate::application::security::jwt::TokenScope;
 use crate::http::extractors::client_context::ClientContext;
+use crate::http::extractors::validated_json::ValidatedJson;
+use crate::http::validation::{Validate, ValidationErrors};
 use crate::shared::{api_codes, api_messages};
 use crate::{
     application::user::login_user::{LoginUserCommand, LoginUserError, LoginUserUseCase},
     http::error::ApiError,
     shared::{response::ApiResponse, state::AppState},
 };
+use crate::shared::error::ApiErrorResponse;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginUserRequest {
     pub email: String,
     pub password: String,
 }
 
-#[derive(Serialize)]
+impl Validate for LoginUserRequest {
+    fn validate(&self, _state: &AppState) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.require("email", &self.email);
+        errors.require("password", &self.password);
+
+        errors.into_result()
+    }
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct LoginResponse {
     pub access_token: String,
     pub refresh_token: String,
     pub token_type: String,
+    /// The access token only allows changing the password.
+    pub password_change_required: bool,
 }
 
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "auth",
+    request_body = LoginUserRequest,
+    responses(
+        (status = 200, description = "Tokens issued", body = ApiResponse<LoginResponse>),
+        (status = 401, description = "Invalid credentials", body = ApiErrorResponse),
+        (status = 429, description = "Too many attempts", body = ApiErrorResponse),
+    )
+)]
 pub async fn login_user(
     State(state): State<AppState>,
     client_ctx: ClientContext,
-    Json(payload): Json<LoginUserRequest>,
+    ValidatedJson(payload): ValidatedJson<LoginUserRequest>,
 ) -> Result<Json<ApiResponse<LoginResponse>>, ApiError> {
     let use_case = LoginUserUseCase::new(
         state.user_repo.clone(),
         state.refresh_token_repo.clone(),
         state.password_hasher.clone(),
         state.audit_logger.clone(),
+        state.login_throttle.clone(),
         state.config.refresh_token_ttl_seconds,
+        state.config.password_max_age,
     );
 
     let cmd = LoginUserCommand {
@@ -49,6 +80,10 @@ pub async fn login_user(
             code: api_codes::auth::INVALID_CREDENTIALS,
             message: api_messages::auth::INVALID_CREDENTIALS,
         },
+        LoginUserError::TooManyAttempts => ApiError::TooManyRequests {
+            code: api_codes::auth::TOO_MANY_LOGIN_ATTEMPTS,
+            message: api_messages::auth::TOO_MANY_LOGIN_ATTEMPTS,
+        },
         _ => ApiError::Internal {
             code: api_codes::auth::LOGIN_FAILED,
             message: api_messages::auth::LOGIN_FAILED,
@@ -58,7 +93,7 @@ pub async fn login_user(
     let access_token =
         state
             .jwt_service
-            .generate(result.user_id)
+            .generate(result.user_id, result.role, result.scope)
             .map_err(|_| ApiError::Internal {
                 code: api_codes::auth::TOKEN_GENERATION_FAILED,
                 message: api_messages::auth::TOKEN_GENERATION_FAILED,
@@ -71,6 +106,7 @@ pub async fn login_user(
             access_token,
             refresh_token: result.refresh_token,
             token_type: "Bearer".to_string(),
+            password_change_required: result.scope == TokenScope::PasswordChange,
         },
     )))
 }
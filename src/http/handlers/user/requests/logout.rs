@@ -1,20 +1,44 @@
 use axum::{Json, extract::State};
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::shared::state::AppState;
 use crate::shared::response::ApiResponse;
 use crate::http::error::ApiError;
+use crate::shared::error::ApiErrorResponse;
 use crate::application::auth::logout::{LogoutUseCase, LogoutError};
+use crate::http::extractors::validated_json::ValidatedJson;
+use crate::http::validation::{Validate, ValidationErrors};
 use crate::shared::{api_codes, api_messages};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LogoutRequest {
     pub refresh_token: String,
 }
 
+impl Validate for LogoutRequest {
+    fn validate(&self, _state: &AppState) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.require("refresh_token", &self.refresh_token);
+
+        errors.into_result()
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = "auth",
+    request_body = LogoutRequest,
+    responses(
+        (status = 200, description = "Refresh token revoked", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "Invalid refresh token", body = ApiErrorResponse),
+    )
+)]
 pub async fn logout(
     State(state): State<AppState>,
-    Json(payload): Json<LogoutRequest>,
+    ValidatedJson(payload): ValidatedJson<LogoutRequest>,
 ) -> Result<Json<ApiResponse<()>>, ApiError> {
 
     let use_case = LogoutUseCase::new(
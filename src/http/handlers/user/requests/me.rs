@@ -10,7 +10,19 @@ use crate::application::user::get_current_user::GetCurrentUserError;
 use crate::http::handlers::user::responses::me_response::MeResponse;
 use crate::shared::{api_codes, api_messages};
 use crate::shared::state::AppState;
+use crate::shared::error::ApiErrorResponse;
 
+#[utoipa::path(
+    get,
+    path = "/me",
+    tag = "users",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Current user", body = ApiResponse<MeResponse>),
+        (status = 401, description = "Not authenticated", body = ApiErrorResponse),
+        (status = 404, description = "User not found", body = ApiErrorResponse),
+    )
+)]
 pub async fn me(
     AuthUser(auth): AuthUser,
     State(state): State<AppState>,
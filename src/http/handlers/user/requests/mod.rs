@@ -1,4 +1,6 @@
 pub mod change_password;
+pub mod force_password_change;
+pub mod import_users;
 pub mod login;
 pub mod logout;
 pub mod me;
@@ -1,36 +1,64 @@
 use axum::Json;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use axum::extract::State;
 
 use crate::shared::state::AppState;
 use crate::shared::response::ApiResponse;
 use crate::http::error::ApiError;
+use crate::shared::error::ApiErrorResponse;
 use crate::application::auth::refresh_access_token::{
     RefreshAccessTokenUseCase, RefreshAccessTokenError,
 };
+use crate::application::security::jwt::TokenScope;
+use crate::http::extractors::validated_json::ValidatedJson;
+use crate::http::validation::{Validate, ValidationErrors};
 use crate::shared::{api_codes, api_messages};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct RefreshRequest {
     pub refresh_token: String,
 }
 
-#[derive(Debug, Serialize)]
+impl Validate for RefreshRequest {
+    fn validate(&self, _state: &AppState) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.require("refresh_token", &self.refresh_token);
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RefreshResponse {
     pub access_token: String,
     pub refresh_token: String,
     pub token_type: String,
+    pub password_change_required: bool,
 }
 
+#[utoipa::path(
+    post,
+    path = "/refresh",
+    tag = "auth",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "Tokens rotated", body = ApiResponse<RefreshResponse>),
+        (status = 401, description = "Invalid refresh token", body = ApiErrorResponse),
+    )
+)]
 pub async fn refresh_token(
     State(state): State<AppState>,
-    Json(payload): Json<RefreshRequest>,
+    ValidatedJson(payload): ValidatedJson<RefreshRequest>,
 ) -> Result<Json<ApiResponse<RefreshResponse>>, ApiError> {
 
     let use_case = RefreshAccessTokenUseCase::new(
         state.refresh_token_repo.clone(),
+        state.user_repo.clone(),
         state.jwt_service.clone(),
         state.config.refresh_token_ttl_seconds,
+        state.config.password_max_age,
     );
 
     let result = use_case
@@ -54,6 +82,7 @@ pub async fn refresh_token(
             access_token: result.access_token,
             refresh_token: result.refresh_token,
             token_type: "Bearer".to_string(),
+            password_change_required: result.scope == TokenScope::PasswordChange,
         },
     )))
 }
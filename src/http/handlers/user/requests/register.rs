@@ -1,43 +1,71 @@
 use axum::Json;
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::http::error::ApiError;
+use crate::shared::error::ApiErrorResponse;
 use crate::shared::response::ApiResponse;
 use crate::shared::state::AppState;
 
 use axum::extract::State;
-use crate::application::security::password_policy::PasswordPolicy;
+use crate::application::security::password_policy::{PasswordContext, PasswordPolicyError};
 use crate::application::user::register_user::{
     RegisterUserCommand, RegisterUserError, RegisterUserUseCase,
 };
+use crate::domain::user::value_objects::{UserEmail, UserName};
+use crate::http::extractors::validated_json::ValidatedJson;
+use crate::http::validation::{Validate, ValidationErrors};
 use crate::shared::{api_codes, api_messages};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct RegisterUserRequest {
     pub name: String,
     pub email: String,
     pub password: String,
 }
 
+impl Validate for RegisterUserRequest {
+    fn validate(&self, state: &AppState) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.check(UserName::new(self.name.clone()));
+        errors.check(UserEmail::new(self.email.clone()));
+
+        let context = PasswordContext {
+            name: Some(&self.name),
+            email: Some(&self.email),
+        };
+
+        if let Err(PasswordPolicyError::WeakPassword(violations)) =
+            state.password_policy.validate(&self.password, context)
+        {
+            errors.weak_password("password", &violations);
+        }
+
+        errors.into_result()
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/register",
+    tag = "auth",
+    request_body = RegisterUserRequest,
+    responses(
+        (status = 200, description = "User registered", body = ApiResponse<serde_json::Value>),
+        (status = 400, description = "Email already registered", body = ApiErrorResponse),
+        (status = 422, description = "Invalid fields", body = ApiErrorResponse),
+    )
+)]
 pub async fn register_user(
     State(state): State<AppState>,
-    Json(payload): Json<RegisterUserRequest>,
+    ValidatedJson(payload): ValidatedJson<RegisterUserRequest>,
 ) -> Result<Json<ApiResponse<()>>, ApiError> {
-    PasswordPolicy::validate(&payload.password)
-        .map_err(|_| ApiError::Validation {
-            code: api_codes::users::WEAK_PASSWORD,
-            message: api_messages::users::WEAK_PASSWORD,
-            errors: [(
-                        "password".to_string(),
-                        vec![api_messages::validator::INVALID_PASSWORD_FORMAT.to_string()],
-                    )]
-                .into_iter()
-                .collect(),
-        })?;
 
     let password_hash = state
         .password_hasher
         .hash(&payload.password)
+        .await
         .map_err(|_| ApiError::Internal {
             code: api_codes::auth::HASHING_FAILED,
             message: api_messages::auth::HASHING_FAILED,
@@ -1,5 +1,6 @@
 use axum::{extract::State, Json};
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::{
     application::user::update_profile::{
@@ -8,18 +9,44 @@ use crate::{
     http::error::ApiError,
     shared::{response::ApiResponse, state::AppState},
 };
+use crate::domain::user::value_objects::UserName;
 use crate::http::extractors::auth_user::AuthUser;
+use crate::http::extractors::validated_json::ValidatedJson;
+use crate::http::validation::{Validate, ValidationErrors};
 use crate::shared::{api_codes, api_messages};
+use crate::shared::error::ApiErrorResponse;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateMeRequest {
     pub name: String,
 }
 
+impl Validate for UpdateMeRequest {
+    fn validate(&self, _state: &AppState) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.check(UserName::new(self.name.clone()));
+
+        errors.into_result()
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/me",
+    tag = "users",
+    security(("bearer_auth" = [])),
+    request_body = UpdateMeRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "Not authenticated", body = ApiErrorResponse),
+        (status = 422, description = "Invalid fields", body = ApiErrorResponse),
+    )
+)]
 pub async fn update_me(
     AuthUser(auth): AuthUser,
     State(state): State<AppState>,
-    Json(payload): Json<UpdateMeRequest>,
+    ValidatedJson(payload): ValidatedJson<UpdateMeRequest>,
 ) -> Result<Json<ApiResponse<()>>, ApiError> {
 
     let use_case = UpdateProfileUseCase::new(state.user_repo.clone());
@@ -0,0 +1,36 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::application::user::import_users::{ImportFailure, ImportUsersResult};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportFailureResponse {
+    pub index: usize,
+    pub email: String,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportUsersResponse {
+    pub imported: usize,
+    pub failed: Vec<ImportFailureResponse>,
+}
+
+impl From<ImportFailure> for ImportFailureResponse {
+    fn from(failure: ImportFailure) -> Self {
+        Self {
+            index: failure.index,
+            email: failure.email,
+            reason: failure.reason.as_str(),
+        }
+    }
+}
+
+impl From<ImportUsersResult> for ImportUsersResponse {
+    fn from(result: ImportUsersResult) -> Self {
+        Self {
+            imported: result.imported,
+            failed: result.failures.into_iter().map(Into::into).collect(),
+        }
+    }
+}
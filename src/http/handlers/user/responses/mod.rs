@@ -1 +1,2 @@
+pub mod import_users_response;
 pub mod me_response;
@@ -0,0 +1,62 @@
+use ipnet::IpNet;
+use std::net::IpAddr;
+
+/// Static allow and deny lists of CIDRs. Denied ranges are rejected
+/// outright; allowed ranges skip rate limiting and bans. An address in both
+/// lists is denied.
+#[derive(Debug, Clone, Default)]
+pub struct IpAccessList {
+    allow: Vec<IpNet>,
+    deny: Vec<IpNet>,
+}
+
+impl IpAccessList {
+    pub fn new(allow: Vec<IpNet>, deny: Vec<IpNet>) -> Self {
+        Self { allow, deny }
+    }
+
+    pub fn is_denied(&self, ip: IpAddr) -> bool {
+        contains(&self.deny, ip)
+    }
+
+    pub fn is_allowed(&self, ip: IpAddr) -> bool {
+        contains(&self.allow, ip)
+    }
+}
+
+fn contains(nets: &[IpNet], ip: IpAddr) -> bool {
+    let ip = ip.to_canonical();
+    nets.iter().any(|net| net.contains(&ip))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(allow: &[&str], deny: &[&str]) -> IpAccessList {
+        let nets = |nets: &[&str]| nets.iter().map(|net| net.parse().unwrap()).collect();
+        IpAccessList::new(nets(allow), nets(deny))
+    }
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    #[test]
+    fn denied_ranges_match_and_other_addresses_do_not() {
+        let access = list(&[], &["198.51.100.0/24", "2001:db8::/32"]);
+
+        assert!(access.is_denied(ip("198.51.100.77")));
+        assert!(access.is_denied(ip("2001:db8::1")));
+        assert!(!access.is_denied(ip("198.51.101.1")));
+        assert!(!access.is_allowed(ip("198.51.101.1")));
+    }
+
+    #[test]
+    fn ipv4_mapped_addresses_match_ipv4_ranges() {
+        let access = list(&["10.0.0.0/8"], &["198.51.100.0/24"]);
+
+        assert!(access.is_denied(ip("::ffff:198.51.100.1")));
+        assert!(access.is_allowed(ip("::ffff:10.1.2.3")));
+    }
+}
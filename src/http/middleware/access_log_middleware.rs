@@ -0,0 +1,317 @@
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use http_body::{Frame, SizeHint};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tracing::Span;
+
+use crate::http::auth_context::AuthContext;
+use crate::shared::state::AppState;
+
+/// Logs one event per request once the response body has been sent. Only
+/// the method, route template, status, timing and sizes are recorded: never
+/// headers, query strings or bodies, so tokens and passwords cannot leak.
+///
+/// `latency_ms` is the time until the response headers were ready, which is
+/// what `access_log_slow_threshold` is compared against, so a long streamed
+/// export is not reported as slow. `bytes` counts the body as it is sent and
+/// is also logged when the client disconnects part way.
+///
+/// Must wrap the whole router, inside `request_id_middleware` so that the
+/// event carries the request id. The route and user id come back from inner
+/// layers through response extensions, see `tag_route` and `auth_middleware`.
+pub async fn access_log_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = req.method().to_string();
+
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ci| ci.0);
+    let client_ip = state
+        .client_ip_resolver
+        .resolve(req.headers(), peer)
+        .map(|ip| ip.to_string());
+
+    let response = next.run(req).await;
+
+    let entry = AccessLogEntry {
+        method,
+        route: response
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|path| path.as_str().to_string()),
+        status: response.status().as_u16(),
+        latency: started.elapsed(),
+        slow_threshold: state.config.access_log_slow_threshold,
+        client_ip,
+        user_id: response
+            .extensions()
+            .get::<AuthContext>()
+            .map(|auth| auth.user_id.to_string()),
+        // The body is polled outside `request_id_middleware`'s span.
+        span: Span::current(),
+    };
+
+    response.map(|inner| {
+        Body::new(LoggedBody {
+            inner,
+            bytes: 0,
+            entry: Some(entry),
+        })
+    })
+}
+
+struct AccessLogEntry {
+    method: String,
+    route: Option<String>,
+    status: u16,
+    latency: Duration,
+    slow_threshold: Duration,
+    client_ip: Option<String>,
+    user_id: Option<String>,
+    span: Span,
+}
+
+impl AccessLogEntry {
+    fn emit(self, bytes: u64) {
+        let _entered = self.span.enter();
+        let route = self.route.as_deref().unwrap_or("unmatched");
+        let latency_ms = self.latency.as_millis() as u64;
+
+        if self.latency >= self.slow_threshold {
+            tracing::warn!(
+                target: "access_log",
+                method = %self.method,
+                route,
+                status = self.status,
+                latency_ms,
+                bytes,
+                client_ip = self.client_ip.as_deref(),
+                user_id = self.user_id.as_deref(),
+                "slow request"
+            );
+        } else {
+            tracing::info!(
+                target: "access_log",
+                method = %self.method,
+                route,
+                status = self.status,
+                latency_ms,
+                bytes,
+                client_ip = self.client_ip.as_deref(),
+                user_id = self.user_id.as_deref(),
+                "request completed"
+            );
+        }
+    }
+}
+
+/// Counts the bytes of the wrapped body and emits the access log entry when
+/// the body ends, fails or is dropped, whichever comes first.
+struct LoggedBody {
+    inner: Body,
+    bytes: u64,
+    entry: Option<AccessLogEntry>,
+}
+
+impl LoggedBody {
+    fn finish(&mut self) {
+        if let Some(entry) = self.entry.take() {
+            entry.emit(self.bytes);
+        }
+    }
+}
+
+impl HttpBody for LoggedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        let polled = Pin::new(&mut self.inner).poll_frame(cx);
+
+        match &polled {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    self.bytes += data.len() as u64;
+                }
+            }
+            Poll::Ready(Some(Err(_))) | Poll::Ready(None) => self.finish(),
+            Poll::Pending => {}
+        }
+
+        if self.inner.is_end_stream() {
+            self.finish();
+        }
+
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for LoggedBody {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Copies the matched route template onto the response for
+/// `access_log_middleware`. Must be installed with `route_layer`.
+pub async fn tag_route(req: Request, next: Next) -> Response {
+    let route = req.extensions().get::<MatchedPath>().cloned();
+
+    let mut response = next.run(req).await;
+
+    if let Some(route) = route {
+        response.extensions_mut().insert(route);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+    use axum::routing::get;
+    use axum::{middleware, Router};
+    use futures_util::stream;
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::Event;
+    use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+    use tracing_subscriber::Layer;
+    use uuid::Uuid;
+
+    use crate::application::security::jwt::TokenScope;
+    use crate::domain::user::value_objects::UserRole;
+    use crate::http::routes::create_router;
+    use crate::http::test_support::{self, send};
+
+    type Fields = HashMap<String, String>;
+
+    /// Records the fields of every `access_log` event.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<Fields>>>);
+
+    impl Captured {
+        fn events(&self) -> Vec<Fields> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    struct FieldVisitor<'a>(&'a mut Fields);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: tracing::Subscriber> Layer<S> for Captured {
+        fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+            if event.metadata().target() == "access_log" {
+                let mut fields = Fields::new();
+                event.record(&mut FieldVisitor(&mut fields));
+                self.0.lock().unwrap().push(fields);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn logs_route_status_and_user_but_not_credentials() {
+        let captured = Captured::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(captured.clone()));
+
+        let state = test_support::state();
+        let user_id = Uuid::now_v7();
+        let token = state
+            .jwt_service
+            .generate(user_id, UserRole::User, TokenScope::Full)
+            .unwrap();
+        let app = create_router(state);
+
+        // An empty body fails validation before the handler touches the database.
+        let request = Request::put("/users/me/change-password")
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = send(&app, request).await;
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        let events = captured.events();
+        assert_eq!(events.len(), 1);
+
+        let event = &events[0];
+        assert_eq!(event["route"], "/users/me/change-password");
+        assert_eq!(event["status"], status.as_u16().to_string());
+        assert_eq!(event["user_id"], user_id.to_string());
+        assert_eq!(event["bytes"], body.len().to_string());
+        assert!(!event.contains_key("authorization"));
+        assert!(event.values().all(|value| !value.contains(&token)));
+    }
+
+    #[tokio::test]
+    async fn counts_streamed_bodies_once_they_finish() {
+        let captured = Captured::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(captured.clone()));
+
+        let app = Router::new()
+            .route(
+                "/export",
+                get(|| async {
+                    let chunks = ["id,action\n", "1,login\n", "2,logout\n"]
+                        .map(|chunk| Ok::<_, Infallible>(Bytes::from(chunk)));
+                    Body::from_stream(stream::iter(chunks))
+                }),
+            )
+            .route_layer(middleware::from_fn(tag_route))
+            .layer(middleware::from_fn_with_state(
+                test_support::state(),
+                access_log_middleware,
+            ));
+
+        let response = send(&app, Request::get("/export").body(Body::empty()).unwrap()).await;
+        assert!(response.body().size_hint().exact().is_none());
+        assert!(captured.events().is_empty());
+
+        to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        let events = captured.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["route"], "/export");
+        assert_eq!(events[0]["bytes"], "27");
+    }
+}
@@ -0,0 +1,46 @@
+use axum::{
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use axum::body::Body;
+use crate::{
+    http::{auth_context::AuthContext, error::ApiError},
+    shared::state::AppState,
+};
+use crate::shared::{api_codes, api_messages};
+
+/// Must run inside `auth_middleware`. The role is read from the database
+/// rather than the token so that demoting an admin takes effect immediately.
+pub async fn admin_middleware(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let auth = req
+        .extensions()
+        .get::<AuthContext>()
+        .ok_or(ApiError::Unauthorized {
+            code: api_codes::auth::UNAUTHORIZED,
+            message: api_messages::auth::UNAUTHORIZED,
+        })?;
+
+    let user = state
+        .user_repo
+        .find_by_id(auth.user_id)
+        .await
+        .map_err(|_| ApiError::Forbidden {
+            code: api_codes::auth::FORBIDDEN,
+            message: api_messages::auth::FORBIDDEN,
+        })?;
+
+    if !user.is_admin() {
+        return Err(ApiError::Forbidden {
+            code: api_codes::auth::FORBIDDEN,
+            message: api_messages::auth::FORBIDDEN,
+        });
+    }
+
+    Ok(next.run(req).await)
+}
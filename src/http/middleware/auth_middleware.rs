@@ -1,24 +1,24 @@
 use axum::{
-    extract::State,
-    http::Request,
+    extract::{MatchedPath, State},
+    http::{HeaderMap, Request},
     middleware::Next,
     response::Response,
 };
 use axum::body::Body;
+use crate::application::security::jwt::TokenScope;
 use crate::{
     http::{auth_context::AuthContext, error::ApiError},
     shared::state::AppState,
 };
 use crate::shared::{api_codes, api_messages};
 
-pub async fn auth_middleware(
-    State(state): State<AppState>,
-    mut req: Request<Body>,
-    next: Next,
-) -> Result<Response, ApiError> {
+/// The only route a `PasswordChange` token may call.
+const PASSWORD_CHANGE_ROUTE: &str = "/users/me/change-password";
 
-    let auth_header = req
-        .headers()
+/// Verifies the bearer token in `headers` without touching the request, so
+/// that layers outside `auth_middleware` can identify the caller too.
+pub fn authenticate(state: &AppState, headers: &HeaderMap) -> Result<AuthContext, ApiError> {
+    let auth_header = headers
         .get(axum::http::header::AUTHORIZATION)
         .ok_or(ApiError::Unauthorized {
             code: api_codes::auth::UNAUTHORIZED,
@@ -47,9 +47,40 @@ pub async fn auth_middleware(
             message: api_messages::auth::UNAUTHORIZED_INVALID_TOKEN,
         })?;
 
-    req.extensions_mut().insert(AuthContext {
+    Ok(AuthContext {
         user_id: claims.sub,
-    });
+        role: claims.role,
+        scope: claims.scope,
+    })
+}
+
+pub async fn auth_middleware(
+    State(state): State<AppState>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    // Reuse the context if `rate_limit_middleware` already verified the token.
+    let auth = match req.extensions().get::<AuthContext>() {
+        Some(auth) => auth.clone(),
+        None => authenticate(&state, req.headers())?,
+    };
+
+    if auth.scope == TokenScope::PasswordChange {
+        let route = req.extensions().get::<MatchedPath>().map(MatchedPath::as_str);
+
+        if route != Some(PASSWORD_CHANGE_ROUTE) {
+            return Err(ApiError::Forbidden {
+                code: api_codes::auth::PASSWORD_CHANGE_REQUIRED,
+                message: api_messages::auth::PASSWORD_CHANGE_REQUIRED,
+            });
+        }
+    }
+
+    req.extensions_mut().insert(auth.clone());
+
+    // Lets `access_log_middleware` attribute the request to the user.
+    let mut response = next.run(req).await;
+    response.extensions_mut().insert(auth);
 
-    Ok(next.run(req).await)
-}
\ No newline at end of file
+    Ok(response)
+}
@@ -0,0 +1,75 @@
+use axum::{
+    extract::{MatchedPath, State},
+    http::{header, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum::body::Body;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::http::error::ApiError;
+use crate::shared::metrics::Metrics;
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+/// Must be installed with `route_layer`, inside `rate_limit_middleware` so
+/// that rate limited requests never take a slot.
+pub async fn concurrency_limit_middleware(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_default();
+
+    let limiter = state.concurrency_limiter.clone();
+    let metrics = state.metrics.clone();
+
+    let permit = limiter.acquire(req.method(), &route).await;
+    metrics
+        .concurrency_queued
+        .store(limiter.queued() as i64, Ordering::Relaxed);
+
+    let Ok(_permit) = permit else {
+        metrics.concurrency_shed.fetch_add(1, Ordering::Relaxed);
+
+        tracing::warn!(method = %req.method(), route, "request shed, server overloaded");
+
+        let mut response = ApiError::ServiceUnavailable {
+            code: api_codes::server::OVERLOADED,
+            message: api_messages::server::OVERLOADED,
+        }
+        .into_response();
+
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(limiter.retry_after().as_secs().max(1)),
+        );
+
+        return response;
+    };
+
+    let _in_flight = InFlight::new(metrics);
+
+    next.run(req).await
+}
+
+/// Keeps the in-flight gauge right when a request is cancelled mid-way.
+struct InFlight(Arc<Metrics>);
+
+impl InFlight {
+    fn new(metrics: Arc<Metrics>) -> Self {
+        metrics.concurrency_in_flight.fetch_add(1, Ordering::Relaxed);
+        Self(metrics)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.concurrency_in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
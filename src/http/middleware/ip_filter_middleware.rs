@@ -0,0 +1,129 @@
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum::body::Body;
+use chrono::Utc;
+use std::net::SocketAddr;
+
+use crate::http::error::ApiError;
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+/// Marks a request from an allowlisted address; `rate_limit_middleware`
+/// lets it through unchecked.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitExempt;
+
+/// Must wrap the whole router with `layer`, outside `rate_limit_middleware`.
+pub async fn ip_filter_middleware(
+    State(state): State<AppState>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ci| ci.0);
+
+    let Some(ip) = state.client_ip_resolver.resolve(req.headers(), peer) else {
+        return next.run(req).await;
+    };
+
+    if state.ip_access.is_denied(ip) {
+        return ApiError::Forbidden {
+            code: api_codes::auth::IP_DENIED,
+            message: api_messages::auth::IP_DENIED,
+        }
+        .into_response();
+    }
+
+    if state.ip_access.is_allowed(ip) {
+        req.extensions_mut().insert(RateLimitExempt);
+        return next.run(req).await;
+    }
+
+    if let Some(ban) = state.auto_ban.find_ban(ip).await {
+        let mut response = ApiError::Forbidden {
+            code: api_codes::auth::IP_BANNED,
+            message: api_messages::auth::IP_BANNED,
+        }
+        .into_response();
+
+        let remaining = (ban.expires_at - Utc::now()).num_seconds().max(0) as u64;
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(remaining));
+
+        return response;
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    use crate::http::middleware::rate_limit_policy::RateLimitPolicy;
+    use crate::http::routes::create_router;
+    use crate::http::test_support::{self, json_body, send, PEER};
+
+    const HEALTH_LIMIT: &str = r#"{
+        "rules": [
+            { "route": "/health", "methods": ["GET"], "key": "ip", "limit": 1, "window_secs": 60 }
+        ]
+    }"#;
+
+    fn app(allow: &[&str], deny: &[&str], ban_threshold: u32) -> axum::Router {
+        let mut config = test_support::config();
+        config.ip_allowlist = allow.iter().map(|net| net.parse().unwrap()).collect();
+        config.ip_denylist = deny.iter().map(|net| net.parse().unwrap()).collect();
+        config.auto_ban.threshold = ban_threshold;
+
+        let policy = RateLimitPolicy::from_json(HEALTH_LIMIT).unwrap();
+        create_router(test_support::state_with(config, policy))
+    }
+
+    async fn health(app: &axum::Router) -> Response {
+        send(app, Request::get("/health").body(Body::empty()).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn denied_ranges_are_rejected() {
+        let app = app(&[], &["192.0.2.0/24"], 0);
+
+        let response = health(&app).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(json_body(response).await["code"], api_codes::auth::IP_DENIED);
+    }
+
+    #[tokio::test]
+    async fn allowlisted_addresses_skip_rate_limiting() {
+        let peer = PEER.parse::<SocketAddr>().unwrap().ip();
+        let app = app(&[&format!("{peer}/32")], &[], 0);
+
+        for _ in 0..3 {
+            assert_eq!(health(&app).await.status(), StatusCode::OK);
+        }
+    }
+
+    #[tokio::test]
+    async fn other_addresses_are_rate_limited_and_then_banned() {
+        let app = app(&["10.0.0.0/8"], &[], 2);
+
+        assert_eq!(health(&app).await.status(), StatusCode::OK);
+        assert_eq!(health(&app).await.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(health(&app).await.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = health(&app).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(response.headers().contains_key(header::RETRY_AFTER));
+        assert_eq!(json_body(response).await["code"], api_codes::auth::IP_BANNED);
+    }
+}
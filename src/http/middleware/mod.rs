@@ -1,4 +1,9 @@
+pub mod access_log_middleware;
+pub mod admin_middleware;
 pub mod auth_middleware;
+pub mod concurrency_limit_middleware;
+pub mod ip_filter_middleware;
 pub mod rate_limit_middleware;
 pub mod rate_limit_policy;
-pub mod rate_limit_key;
\ No newline at end of file
+pub mod rate_limit_key;
+pub mod request_id_middleware;
@@ -1,18 +1,43 @@
 use axum::body::Body;
+use axum::extract::ConnectInfo;
 use axum::http::Request;
+use std::net::SocketAddr;
 
-pub fn extract_client_identifier(req: &Request<Body>) -> String {
-    if let Some(value) = req.headers().get("x-user-id") {
-        if let Ok(v) = value.to_str() {
-            return format!("user:{v}");
-        }
-    }
+use crate::http::auth_context::AuthContext;
+use crate::http::client_ip::ClientIpResolver;
+use crate::http::middleware::rate_limit_policy::RateLimitKeyStrategy;
+
+pub fn extract_client_identifier(req: &Request<Body>, resolver: &ClientIpResolver) -> String {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ci| ci.0);
 
-    if let Some(value) = req.headers().get("x-forwarded-for") {
-        if let Ok(v) = value.to_str() {
-            return format!("ip:{v}");
-        }
+    match resolver.resolve(req.headers(), peer) {
+        Some(ip) => format!("ip:{ip}"),
+        None => "unknown".to_string(),
     }
+}
 
-    "unknown".to_string()
+/// `caller` must come from a verified token; `email` must already be
+/// normalized and is only used by `IpEmail`.
+pub fn identity_for(
+    strategy: RateLimitKeyStrategy,
+    req: &Request<Body>,
+    resolver: &ClientIpResolver,
+    caller: Option<&AuthContext>,
+    email: Option<&str>,
+) -> String {
+    match strategy {
+        RateLimitKeyStrategy::Ip => extract_client_identifier(req, resolver),
+        RateLimitKeyStrategy::User => match caller {
+            Some(auth) => format!("user:{}", auth.user_id),
+            None => extract_client_identifier(req, resolver),
+        },
+        RateLimitKeyStrategy::IpEmail => format!(
+            "{}:email:{}",
+            extract_client_identifier(req, resolver),
+            email.unwrap_or("")
+        ),
+    }
 }
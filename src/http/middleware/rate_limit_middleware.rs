@@ -1,38 +1,195 @@
-use axum::{extract::State, http::Request, middleware::Next, response::IntoResponse};
+use axum::{
+    extract::{ConnectInfo, MatchedPath, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 
+use crate::domain::rate_limit::decision::RateLimitDecision;
+use crate::domain::user::value_objects::UserEmail;
 use crate::http::error::ApiError;
-use crate::http::middleware::rate_limit_key::extract_client_identifier;
-use crate::http::middleware::rate_limit_policy::policy_for_path;
+use crate::http::middleware::auth_middleware::authenticate;
+use crate::http::middleware::ip_filter_middleware::RateLimitExempt;
+use crate::http::middleware::rate_limit_key::identity_for;
+use crate::http::middleware::rate_limit_policy::{RateLimitFailMode, RateLimitKeyStrategy};
 use crate::shared::state::AppState;
 use axum::body::Body;
 use crate::shared::{api_codes, api_messages};
 
+const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// Upper bound on the body buffered to find the `email` of `ip_email` rules.
+const MAX_KEY_BODY_BYTES: usize = 64 * 1024;
+
+/// Must be installed with `route_layer` so that `MatchedPath` is available.
 pub async fn rate_limit_middleware(
     State(state): State<AppState>,
     req: Request<Body>,
     next: Next,
-) -> impl IntoResponse {
-    let path = req.uri().path();
+) -> Response {
+    let Some(route) = req.extensions().get::<MatchedPath>().cloned() else {
+        return next.run(req).await;
+    };
 
-    let Some(rule) = policy_for_path(path) else {
+    if req.extensions().get::<RateLimitExempt>().is_some() {
         return next.run(req).await;
+    }
+    let method = req.method().clone();
+
+    let policy = state.rate_limit_policy.clone();
+    let rules: Vec<_> = policy.rules_for(&method, route.as_str()).collect();
+
+    if rules.is_empty() {
+        return next.run(req).await;
+    }
+
+    let (mut req, email) = if rules.iter().any(|r| r.key == RateLimitKeyStrategy::IpEmail) {
+        match read_email(req).await {
+            Ok(read) => read,
+            Err(err) => return err.into_response(),
+        }
+    } else {
+        (req, None)
     };
 
-    let key = format!("rl:{}:{}", path, extract_client_identifier(&req));
+    // This layer runs before `auth_middleware`, so the token is verified
+    // here and the result handed on through the request extensions. Missing
+    // or invalid tokens are treated as anonymous and left for
+    // `auth_middleware` to reject.
+    let caller = if rules.iter().any(|r| r.key == RateLimitKeyStrategy::User) {
+        authenticate(&state, req.headers()).ok()
+    } else {
+        None
+    };
 
-    let allowed = state
-        .rate_limit_store
-        .check(key, rule.limit, rule.window)
-        .await
-        .unwrap_or(false);
+    if let Some(caller) = &caller {
+        req.extensions_mut().insert(caller.clone());
+    }
+
+    // Every matching rule must allow the request; the headers describe the
+    // rule closest to its limit.
+    let mut tightest: Option<RateLimitDecision> = None;
+
+    for rule in rules {
+        let identity = identity_for(
+            rule.key,
+            &req,
+            &state.client_ip_resolver,
+            caller.as_ref(),
+            email.as_deref(),
+        );
+        let key = format!("rl:{}:{}:{}:{}", method, route.as_str(), rule.key.as_str(), identity);
+
+        let quota = match rule.key {
+            RateLimitKeyStrategy::User => rule.quota_for(caller.as_ref().map(|c| c.role)),
+            _ => rule.quota_for(None),
+        };
+
+        let decision = match state
+            .rate_limit_store
+            .check(key, quota.limit, quota.window, rule.algorithm)
+            .await
+        {
+            Ok(decision) => decision,
+            Err(_) => {
+                state.metrics.rate_limit_store_errors.fetch_add(1, Ordering::Relaxed);
+
+                tracing::warn!(
+                    route = route.as_str(),
+                    key = rule.key.as_str(),
+                    fail_mode = rule.fail_mode.as_str(),
+                    "rate limit store unavailable"
+                );
+
+                match rule.fail_mode {
+                    RateLimitFailMode::Open => continue,
+                    RateLimitFailMode::Closed => return too_many_requests(),
+                }
+            }
+        };
+
+        if !decision.allowed {
+            // Only limits keyed on the address count towards a ban: one user
+            // exhausting their own quota must not ban everyone behind the
+            // same NAT. Anonymous callers of `user` rules are keyed on IP.
+            let keyed_on_ip = rule.key != RateLimitKeyStrategy::User || caller.is_none();
+
+            if keyed_on_ip {
+                let peer = req
+                    .extensions()
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ci| ci.0);
 
-    if !allowed {
-        return ApiError::TooManyRequests {
-            code: api_codes::auth::RATE_LIMIT_EXCEEDED,
-            message: api_messages::auth::RATE_LIMIT_EXCEEDED,
+                if let Some(ip) = state.client_ip_resolver.resolve(req.headers(), peer) {
+                    state.auto_ban.record_violation(ip).await;
+                }
+            }
+
+            let mut response = too_many_requests();
+            apply_headers(response.headers_mut(), &decision);
+            return response;
+        }
+
+        if tightest.is_none_or(|t| decision.remaining < t.remaining) {
+            tightest = Some(decision);
         }
-        .into_response();
     }
 
-    next.run(req).await
+    let mut response = next.run(req).await;
+
+    if let Some(decision) = tightest {
+        apply_headers(response.headers_mut(), &decision);
+    }
+
+    response
+}
+
+/// Buffers the body to pull out a normalized `email`, then rebuilds the
+/// request so the handler can still extract it.
+async fn read_email(req: Request<Body>) -> Result<(Request<Body>, Option<String>), ApiError> {
+    let (parts, body) = req.into_parts();
+
+    let bytes = axum::body::to_bytes(body, MAX_KEY_BODY_BYTES)
+        .await
+        .map_err(|_| ApiError::BadRequest {
+            code: api_codes::validator::INVALID_REQUEST_BODY,
+            message: api_messages::validator::INVALID_REQUEST_BODY,
+        })?;
+
+    let email = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|v| v.get("email").and_then(|e| e.as_str()).map(UserEmail::normalize));
+
+    Ok((Request::from_parts(parts, Body::from(bytes)), email))
+}
+
+fn too_many_requests() -> Response {
+    ApiError::TooManyRequests {
+        code: api_codes::auth::RATE_LIMIT_EXCEEDED,
+        message: api_messages::auth::RATE_LIMIT_EXCEEDED,
+    }
+    .into_response()
+}
+
+/// Delta-seconds are rounded up so that clients never retry too early.
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_millis().div_ceil(1000) as u64
+}
+
+fn apply_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(decision.limit));
+    headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(
+        RATE_LIMIT_RESET,
+        HeaderValue::from(ceil_secs(decision.reset_after)),
+    );
+
+    if let Some(retry_after) = decision.retry_after {
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(ceil_secs(retry_after)));
+    }
 }
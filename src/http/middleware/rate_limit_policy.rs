@@ -1,32 +1,287 @@
+use axum::http::Method;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::time::Duration;
+use thiserror::Error;
 
+use crate::domain::rate_limit::algorithm::RateLimitAlgorithm;
+use crate::domain::user::value_objects::UserRole;
+
+/// Rules shipped with the binary, used when `RATE_LIMIT_CONFIG_PATH` is unset.
+const DEFAULT_RULES: &str = include_str!("../../../config/rate_limits.json");
+
+#[derive(Debug, Error)]
+pub enum RateLimitPolicyError {
+    #[error("failed to read rate limit config {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("invalid rate limit config: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("invalid HTTP method `{0}` in rate limit rule")]
+    InvalidMethod(String),
+
+    #[error("rate limit rule for `{route}` has a zero limit or window")]
+    EmptyQuota { route: String },
+
+    #[error("unknown role `{0}` in rate limit rule")]
+    InvalidRole(String),
+
+    #[error("rate limit rule `{method} {route}` does not match any route")]
+    UnknownRoute { method: String, route: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKeyStrategy {
+    /// Client IP address.
+    Ip,
+    /// Verified user id from the bearer token, falling back to the client
+    /// IP for anonymous requests.
+    User,
+    /// Client IP combined with the `email` field of the JSON body.
+    IpEmail,
+}
+
+impl RateLimitKeyStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitKeyStrategy::Ip => "ip",
+            RateLimitKeyStrategy::User => "user",
+            RateLimitKeyStrategy::IpEmail => "ip_email",
+        }
+    }
+}
+
+/// What to do with a request when its rule cannot be checked because Redis
+/// is failing or its circuit is open. Not consulted when
+/// `RATE_LIMIT_LOCAL_FALLBACK` answers from the in-memory store instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitFailMode {
+    /// Let the request through unlimited.
+    Open,
+    /// Reject the request.
+    #[default]
+    Closed,
+}
+
+impl RateLimitFailMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitFailMode::Open => "open",
+            RateLimitFailMode::Closed => "closed",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitConfigFile {
+    rules: Vec<RateLimitRuleConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitRuleConfig {
+    route: String,
+    #[serde(default)]
+    methods: Vec<String>,
+    key: RateLimitKeyStrategy,
+    limit: u32,
+    window_secs: u64,
+    #[serde(default)]
+    algorithm: RateLimitAlgorithm,
+    #[serde(default)]
+    fail_mode: RateLimitFailMode,
+    /// Per-role overrides for authenticated callers of `user` rules.
+    #[serde(default)]
+    roles: HashMap<String, RateLimitQuotaConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitQuotaConfig {
+    limit: u32,
+    window_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitQuota {
+    pub limit: u32,
+    pub window: Duration,
+}
+
+#[derive(Debug, Clone)]
 pub struct RateLimitRule {
+    /// Route template as registered in the router, e.g. `/users/me`.
+    pub route: String,
+    /// Empty means every method.
+    pub methods: Vec<Method>,
+    pub key: RateLimitKeyStrategy,
     pub limit: u32,
     pub window: Duration,
+    pub algorithm: RateLimitAlgorithm,
+    pub fail_mode: RateLimitFailMode,
+    pub role_quotas: HashMap<UserRole, RateLimitQuota>,
+}
+
+impl RateLimitRule {
+    /// Quota for a caller with `role`, or for an anonymous caller.
+    pub fn quota_for(&self, role: Option<UserRole>) -> RateLimitQuota {
+        role.and_then(|role| self.role_quotas.get(&role).copied())
+            .unwrap_or(RateLimitQuota {
+                limit: self.limit,
+                window: self.window,
+            })
+    }
+
+    fn matches(&self, method: &Method, route: &str) -> bool {
+        self.route == route && (self.methods.is_empty() || self.methods.contains(method))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitPolicy {
+    rules: Vec<RateLimitRule>,
+}
+
+impl RateLimitPolicy {
+    /// Loads rules from `path`, or the bundled defaults when `path` is `None`.
+    pub fn load(path: Option<&str>) -> Result<Self, RateLimitPolicyError> {
+        match path {
+            Some(path) => {
+                let raw = std::fs::read_to_string(path).map_err(|source| {
+                    RateLimitPolicyError::Io {
+                        path: path.to_string(),
+                        source,
+                    }
+                })?;
+                Self::from_json(&raw)
+            }
+            None => Self::from_json(DEFAULT_RULES),
+        }
+    }
+
+    pub fn from_json(raw: &str) -> Result<Self, RateLimitPolicyError> {
+        let file: RateLimitConfigFile = serde_json::from_str(raw)?;
+
+        let rules = file
+            .rules
+            .into_iter()
+            .map(|rule| {
+                let empty = rule.limit == 0
+                    || rule.window_secs == 0
+                    || rule.roles.values().any(|q| q.limit == 0 || q.window_secs == 0);
+
+                if empty {
+                    return Err(RateLimitPolicyError::EmptyQuota { route: rule.route });
+                }
+
+                let role_quotas = rule
+                    .roles
+                    .iter()
+                    .map(|(role, quota)| {
+                        let role = UserRole::parse(role)
+                            .map_err(|_| RateLimitPolicyError::InvalidRole(role.clone()))?;
+
+                        Ok((
+                            role,
+                            RateLimitQuota {
+                                limit: quota.limit,
+                                window: Duration::from_secs(quota.window_secs),
+                            },
+                        ))
+                    })
+                    .collect::<Result<HashMap<_, _>, RateLimitPolicyError>>()?;
+
+                let methods = rule
+                    .methods
+                    .iter()
+                    .map(|m| {
+                        Method::from_bytes(m.to_ascii_uppercase().as_bytes())
+                            .map_err(|_| RateLimitPolicyError::InvalidMethod(m.clone()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(RateLimitRule {
+                    route: rule.route,
+                    methods,
+                    key: rule.key,
+                    limit: rule.limit,
+                    window: Duration::from_secs(rule.window_secs),
+                    algorithm: rule.algorithm,
+                    fail_mode: rule.fail_mode,
+                    role_quotas,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { rules })
+    }
+
+    /// Fails on the first rule that names a route/method pair the router
+    /// does not serve, so typos surface at startup instead of silently
+    /// disabling a limit.
+    pub fn validate_routes(&self, routes: &[(Method, String)]) -> Result<(), RateLimitPolicyError> {
+        for rule in &self.rules {
+            let unknown = |method: &str| RateLimitPolicyError::UnknownRoute {
+                method: method.to_string(),
+                route: rule.route.clone(),
+            };
+
+            if rule.methods.is_empty() {
+                if !routes.iter().any(|(_, path)| *path == rule.route) {
+                    return Err(unknown("*"));
+                }
+                continue;
+            }
+
+            for method in &rule.methods {
+                if !routes.iter().any(|(m, path)| m == method && *path == rule.route) {
+                    return Err(unknown(method.as_str()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn rules_for<'a>(
+        &'a self,
+        method: &'a Method,
+        route: &'a str,
+    ) -> impl Iterator<Item = &'a RateLimitRule> + 'a {
+        self.rules.iter().filter(move |rule| rule.matches(method, route))
+    }
 }
 
-pub fn policy_for_path(path: &str) -> Option<RateLimitRule> {
-    match path {
-        "/login" => Some(RateLimitRule {
-            limit: 5,
-            window: Duration::from_secs(60),
-        }),
-        "/logout" => Some(RateLimitRule {
-            limit: 5,
-            window: Duration::from_secs(60),
-        }),
-        "/refresh" => Some(RateLimitRule {
-            limit: 10,
-            window: Duration::from_secs(60),
-        }),
-        "/register" => Some(RateLimitRule {
-            limit: 10,
-            window: Duration::from_secs(60),
-        }),
-        "/change-password" => Some(RateLimitRule {
-            limit: 10,
-            window: Duration::from_secs(60),
-        }),
-        _ => None,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::routes::served_routes;
+
+    #[test]
+    fn default_rules_name_served_routes() {
+        let policy = RateLimitPolicy::load(None).unwrap();
+
+        policy.validate_routes(&served_routes()).unwrap();
+    }
+
+    #[test]
+    fn shipped_config_names_served_routes() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/config/rate_limits.json");
+        let policy = RateLimitPolicy::load(Some(path)).unwrap();
+
+        policy.validate_routes(&served_routes()).unwrap();
+    }
+
+    #[test]
+    fn unknown_route_is_rejected() {
+        let policy = RateLimitPolicy::load(None).unwrap();
+
+        let err = policy.validate_routes(&[(Method::GET, "/health".to_string())]);
+
+        assert!(matches!(err, Err(RateLimitPolicyError::UnknownRoute { .. })));
     }
 }
@@ -0,0 +1,90 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::shared::request_id;
+
+pub static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Must be the outermost layer so that everything below, including error
+/// responses and audit entries, sees the id.
+///
+/// A UUID sent by the client or a proxy is kept; anything else is replaced
+/// with a fresh UUIDv7 so the id stays safe to log and index.
+pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
+    let id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value).ok())
+        .unwrap_or_else(Uuid::now_v7);
+
+    let header = HeaderValue::from_str(&id.to_string()).expect("uuid is a valid header value");
+    req.headers_mut().insert(X_REQUEST_ID.clone(), header.clone());
+
+    let span = tracing::info_span!("request", request_id = %id);
+
+    let mut response = request_id::scope(id, next.run(req).instrument(span)).await;
+    response.headers_mut().insert(X_REQUEST_ID.clone(), header);
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+
+    use crate::http::routes::create_router;
+    use crate::http::test_support::{self, json_body, send};
+
+    async fn get(path: &str, request_id: Option<&str>) -> Response {
+        let app = create_router(test_support::state());
+
+        let mut request = Request::get(path);
+        if let Some(id) = request_id {
+            request = request.header(&X_REQUEST_ID, id);
+        }
+
+        send(&app, request.body(Body::empty()).unwrap()).await
+    }
+
+    fn response_id(response: &Response) -> Uuid {
+        let header = response.headers()[&X_REQUEST_ID].to_str().unwrap();
+        Uuid::parse_str(header).unwrap()
+    }
+
+    #[tokio::test]
+    async fn incoming_uuids_are_echoed() {
+        let id = Uuid::parse_str("0b9f3c6e-4f1a-4c2d-9e8b-7a6d5c4b3a21").unwrap();
+
+        let response = get("/health", Some(&id.to_string())).await;
+
+        assert_eq!(response_id(&response), id);
+    }
+
+    #[tokio::test]
+    async fn other_values_are_replaced_with_a_v7_id() {
+        for sent in [Some("not-a-uuid"), None] {
+            let response = get("/health", sent).await;
+
+            assert_eq!(response_id(&response).get_version_num(), 7);
+        }
+    }
+
+    #[tokio::test]
+    async fn error_bodies_carry_the_response_id() {
+        let response = get("/no-such-route", Some("not-a-uuid")).await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let id = response_id(&response);
+        assert_eq!(json_body(response).await["request_id"], id.to_string());
+    }
+}
@@ -1,6 +1,16 @@
 pub mod auth_context;
+pub mod client_ip;
+pub mod concurrency;
+pub mod ip_access;
 pub mod middleware;
+pub mod openapi;
+pub mod problem;
 pub mod error;
 pub mod extractors;
 pub mod handlers;
+pub mod rejection;
 pub mod routes;
+pub mod validation;
+
+#[cfg(test)]
+pub mod test_support;
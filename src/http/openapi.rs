@@ -0,0 +1,53 @@
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::http::problem::ProblemDetails;
+use crate::shared::error::ApiErrorResponse;
+
+/// Document-level parts of the OpenAPI spec. Paths are added by
+/// `routes::api_routes` as routes are registered; the result is served at
+/// `/openapi.json` and written by `axum-api openapi`. CI fails when the
+/// checked-in `openapi.json` no longer matches.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "axum-api",
+        description = "User accounts, authentication and administration."
+    ),
+    components(schemas(ApiErrorResponse, ProblemDetails)),
+    modifiers(&BearerAuth, &NoLicense),
+    tags(
+        (name = "auth", description = "Registration, login and tokens"),
+        (name = "users", description = "The authenticated user's account"),
+        (name = "admin", description = "Admin-only operations"),
+        (name = "system", description = "Health and metrics"),
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+/// utoipa fills `info.license` from Cargo.toml, which has none.
+struct NoLicense;
+
+impl Modify for NoLicense {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.info.license = None;
+    }
+}
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
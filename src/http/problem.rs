@@ -0,0 +1,193 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::shared::state::AppState;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Used for `type` URIs outside of a request.
+const DEFAULT_TYPE_BASE: &str = "/problems";
+
+/// What `ApiError::into_response` needs to know about the request it
+/// answers. Set per request by `error_format_middleware`.
+#[derive(Debug, Clone)]
+pub struct ErrorContext {
+    pub problem_json: bool,
+    pub type_base: Arc<str>,
+}
+
+impl Default for ErrorContext {
+    fn default() -> Self {
+        Self {
+            problem_json: false,
+            type_base: Arc::from(DEFAULT_TYPE_BASE),
+        }
+    }
+}
+
+tokio::task_local! {
+    static ERROR_CONTEXT: ErrorContext;
+}
+
+/// The current request's context, or the default envelope format outside
+/// of a request.
+pub fn error_context() -> ErrorContext {
+    ERROR_CONTEXT.try_with(Clone::clone).unwrap_or_default()
+}
+
+/// RFC 9457 problem details, with validation errors as the `errors`
+/// extension member and the API code as `code`.
+#[derive(Serialize, ToSchema)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    pub title: String,
+    pub status: u16,
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<HashMap<String, Vec<String>>>,
+}
+
+/// `WEAK_PASSWORD` under `/problems` becomes `/problems/weak-password`.
+pub fn type_uri(base: &str, code: &str) -> String {
+    format!(
+        "{}/{}",
+        base.trim_end_matches('/'),
+        code.to_ascii_lowercase().replace('_', "-")
+    )
+}
+
+/// Must wrap every other layer except `request_id_middleware`, so that all
+/// error responses, including those from middleware and caught panics, see
+/// the context.
+pub async fn error_format_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let context = ErrorContext {
+        problem_json: prefers_problem_json(req.headers()),
+        type_base: state.config.problem_type_base.clone(),
+    };
+
+    ERROR_CONTEXT.scope(context, next.run(req)).await
+}
+
+/// Problem details are only sent when asked for explicitly and ranked at
+/// least as high as plain JSON; wildcards keep the envelope.
+fn prefers_problem_json(headers: &HeaderMap) -> bool {
+    let Some(accept) = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    let mut problem = 0.0;
+    let mut json = 0.0;
+
+    for range in accept.split(',') {
+        let mut params = range.split(';').map(str::trim);
+        let media = params.next().unwrap_or_default();
+
+        let quality = params
+            .find_map(|param| param.strip_prefix("q="))
+            .and_then(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        if media.eq_ignore_ascii_case(PROBLEM_JSON) {
+            problem = quality;
+        } else if media.eq_ignore_ascii_case("application/json") {
+            json = quality;
+        }
+    }
+
+    problem > 0.0 && problem >= json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{HeaderValue, StatusCode};
+    use uuid::Uuid;
+
+    use crate::http::routes::create_router;
+    use crate::http::test_support::{self, json_body, send};
+    use crate::shared::{api_codes, api_messages};
+
+    fn accepts(accept: Option<&str>) -> bool {
+        let mut headers = HeaderMap::new();
+        if let Some(accept) = accept {
+            headers.insert(header::ACCEPT, HeaderValue::from_str(accept).unwrap());
+        }
+
+        prefers_problem_json(&headers)
+    }
+
+    #[test]
+    fn envelope_without_an_accept_header() {
+        assert!(!accepts(None));
+        assert!(!accepts(Some("*/*")));
+    }
+
+    #[test]
+    fn problem_json_when_asked_for_alone() {
+        assert!(accepts(Some("application/problem+json")));
+    }
+
+    #[test]
+    fn envelope_when_plain_json_ranks_higher() {
+        assert!(!accepts(Some("application/json;q=1, application/problem+json;q=0.5")));
+        assert!(accepts(Some("application/json;q=0.5, application/problem+json")));
+    }
+
+    #[test]
+    fn zero_quality_refuses_problem_json() {
+        assert!(!accepts(Some("application/problem+json;q=0")));
+    }
+
+    #[test]
+    fn media_types_are_case_insensitive() {
+        assert!(accepts(Some("Application/Problem+JSON")));
+    }
+
+    #[tokio::test]
+    async fn validation_errors_render_as_problem_details() {
+        let app = create_router(test_support::state());
+        let request_id = Uuid::now_v7();
+
+        let request = Request::post("/auth/register")
+            .header(header::ACCEPT, PROBLEM_JSON)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-request-id", request_id.to_string())
+            .body(Body::from(
+                r#"{"name": "Ada", "email": "not an email", "password": "Velvet-Harbor-Quietly-91"}"#,
+            ))
+            .unwrap();
+
+        let response = send(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+
+        let body = json_body(response).await;
+
+        assert_eq!(body["type"], "/problems/validation-error");
+        assert_eq!(body["title"], api_messages::validator::INVALID_FIELDS);
+        assert_eq!(body["status"], 422);
+        assert_eq!(body["code"], api_codes::validator::VALIDATION_ERROR);
+        assert_eq!(body["instance"], request_id.to_string());
+        assert!(body["errors"]["email"].is_array());
+    }
+}
@@ -0,0 +1,43 @@
+use axum::extract::rejection::{PathRejection, QueryRejection};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use std::any::Any;
+
+use crate::http::error::ApiError;
+use crate::shared::{api_codes, api_messages};
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        match rejection.status() {
+            StatusCode::BAD_REQUEST => ApiError::BadRequest {
+                code: api_codes::validator::INVALID_QUERY,
+                message: api_messages::validator::INVALID_QUERY,
+            },
+            _ => internal(),
+        }
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection.status() {
+            StatusCode::BAD_REQUEST => ApiError::BadRequest {
+                code: api_codes::validator::INVALID_PATH_PARAMETER,
+                message: api_messages::validator::INVALID_PATH_PARAMETER,
+            },
+            _ => internal(),
+        }
+    }
+}
+
+/// Used by `CatchPanicLayer`. The panic itself is logged by the panic hook.
+pub fn panic_response(_panic: Box<dyn Any + Send + 'static>) -> Response {
+    internal().into_response()
+}
+
+fn internal() -> ApiError {
+    ApiError::Internal {
+        code: api_codes::server::INTERNAL_ERROR,
+        message: api_messages::server::INTERNAL_ERROR,
+    }
+}
@@ -1,45 +1,165 @@
-use axum::{
-    routing::{get, post, put},
-    Router,
-};
+use axum::{http::Method, Router};
 
-use crate::http::handlers::health;
+use crate::http::handlers::audit::requests::{export, list};
+use crate::http::handlers::ban::requests as ban;
+use crate::http::handlers::{fallback, health, metrics};
+use crate::http::openapi::ApiDoc;
+use crate::http::{problem, rejection};
 use crate::shared::state::AppState;
 
 use crate::http::handlers::user::requests::{
-    change_password, login, logout, me, refresh, register, update_me,
+    change_password, force_password_change, import_users, login, logout, me, refresh, register, update_me,
 };
+use crate::http::middleware::access_log_middleware;
+use crate::http::middleware::admin_middleware;
 use crate::http::middleware::auth_middleware;
+use crate::http::middleware::concurrency_limit_middleware;
+use crate::http::middleware::ip_filter_middleware;
 use crate::http::middleware::rate_limit_middleware;
+use crate::http::middleware::request_id_middleware;
 use axum::middleware;
+use tower_http::catch_panic::CatchPanicLayer;
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_swagger_ui::SwaggerUi;
 
-pub fn create_router(state: AppState) -> Router {
-    let auth_routes = Router::new()
-        .route("/register", post(register::register_user))
-        .route("/login", post(login::login_user))
-        .route("/logout", post(logout::logout))
-        .route("/refresh", post(refresh::refresh_token))
-        .layer(middleware::from_fn_with_state(
+const DOCS_PATH: &str = "/docs";
+const OPENAPI_PATH: &str = "/openapi.json";
+
+/// Every documented route. `routes!` registers each handler under the
+/// method and path of its `#[utoipa::path]`, so the router and the OpenAPI
+/// document are built from the same list. Group middleware is only added
+/// when `state` is given, which lets the document be built without one.
+fn api_routes(state: Option<&AppState>) -> OpenApiRouter<AppState> {
+    let auth_routes = OpenApiRouter::new()
+        .routes(routes!(register::register_user))
+        .routes(routes!(login::login_user))
+        .routes(routes!(logout::logout))
+        .routes(routes!(refresh::refresh_token));
+
+    let mut user_routes = OpenApiRouter::new()
+        .routes(routes!(me::me, update_me::update_me))
+        .routes(routes!(change_password::change_password));
+
+    let mut admin_routes = OpenApiRouter::new()
+        .routes(routes!(list::list_audit_logs))
+        .routes(routes!(export::export_audit_logs))
+        .routes(routes!(ban::list::list_bans))
+        .routes(routes!(import_users::import_users))
+        .routes(routes!(force_password_change::force_password_change))
+        .routes(routes!(ban::lift::lift_ban));
+
+    if let Some(state) = state {
+        user_routes = user_routes.layer(middleware::from_fn_with_state(
             state.clone(),
-            rate_limit_middleware::rate_limit_middleware,
+            auth_middleware::auth_middleware,
         ));
 
-    let user_routes = Router::new()
-        .route("/me", get(me::me))
-        .route("/me", put(update_me::update_me))
-        .route("/me/change-password", put(change_password::change_password))
+        admin_routes = admin_routes
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                admin_middleware::admin_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware::auth_middleware,
+            ));
+    }
+
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .routes(routes!(health::health_check))
+        .routes(routes!(metrics::metrics))
+        .nest("/auth", auth_routes)
+        .nest("/users", user_routes)
+        .nest("/admin", admin_routes)
+}
+
+/// OpenAPI document for every route served by `create_router`.
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    api_routes(None).into_openapi()
+}
+
+/// Every route served by `create_router`, as full route templates. Used to
+/// validate configuration that refers to routes.
+pub fn served_routes() -> Vec<(Method, String)> {
+    let mut routes = vec![
+        (Method::GET, OPENAPI_PATH.to_string()),
+        (Method::GET, DOCS_PATH.to_string()),
+    ];
+
+    for (path, item) in openapi().paths.paths {
+        let operations = [
+            (Method::GET, &item.get),
+            (Method::PUT, &item.put),
+            (Method::POST, &item.post),
+            (Method::DELETE, &item.delete),
+            (Method::PATCH, &item.patch),
+            (Method::HEAD, &item.head),
+            (Method::OPTIONS, &item.options),
+            (Method::TRACE, &item.trace),
+        ];
+
+        routes.extend(
+            operations
+                .into_iter()
+                .filter(|(_, operation)| operation.is_some())
+                .map(|(method, _)| (method, path.clone())),
+        );
+    }
+
+    routes
+}
+
+pub fn create_router(state: AppState) -> Router {
+    let (router, openapi) = api_routes(Some(&state)).split_for_parts();
+
+    router
+        .merge(SwaggerUi::new(DOCS_PATH).url(OPENAPI_PATH, openapi))
+        .fallback(fallback::not_found)
+        .method_not_allowed_fallback(fallback::method_not_allowed)
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            concurrency_limit_middleware::concurrency_limit_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware::rate_limit_middleware,
+        ))
+        .route_layer(middleware::from_fn(access_log_middleware::tag_route))
         .layer(middleware::from_fn_with_state(
             state.clone(),
-            auth_middleware::auth_middleware,
+            ip_filter_middleware::ip_filter_middleware,
         ))
+        .layer(CatchPanicLayer::custom(rejection::panic_response))
         .layer(middleware::from_fn_with_state(
             state.clone(),
-            rate_limit_middleware::rate_limit_middleware,
-        ));
-
-    Router::new()
-        .route("/health", get(health::health_check))
-        .nest("/auth", auth_routes)
-        .nest("/users", user_routes)
+            problem::error_format_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            access_log_middleware::access_log_middleware,
+        ))
+        .layer(middleware::from_fn(
+            request_id_middleware::request_id_middleware,
+        ))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn served_routes_carry_their_group_prefix() {
+        let routes = served_routes();
+        let served = |method: Method, path: &str| routes.contains(&(method, path.to_string()));
+
+        assert!(served(Method::GET, "/health"));
+        assert!(served(Method::POST, "/auth/login"));
+        assert!(served(Method::GET, "/users/me"));
+        assert!(served(Method::PUT, "/users/me"));
+        assert!(served(Method::DELETE, "/admin/bans/{ip}"));
+        assert!(served(Method::GET, OPENAPI_PATH));
+        assert!(!served(Method::GET, "/login"));
+    }
+}
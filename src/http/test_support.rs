@@ -0,0 +1,229 @@
+//! Application state for tests that drive the router: in-memory rate limit
+//! and ban stores, no Redis, and a Postgres pool that only connects if a
+//! handler actually queries it.
+
+use async_trait::async_trait;
+use axum::body::{to_bytes, Body};
+use axum::extract::ConnectInfo;
+use axum::http::Request;
+use axum::response::Response;
+use axum::Router;
+use futures_util::{stream, StreamExt};
+use sqlx::postgres::PgPoolOptions;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tower::ServiceExt;
+
+use crate::application::audit::audit_logger::AuditLogger;
+use crate::application::auth::login_throttle::{LoginThrottle, LoginThrottleConfig};
+use crate::application::rate_limit::auto_ban::{AutoBan, AutoBanConfig};
+use crate::application::security::password_policy::{PasswordPolicy, PasswordPolicyConfig};
+use crate::domain::audit::entity::AuditLog;
+use crate::domain::audit::filter::AuditLogFilter;
+use crate::domain::audit::repository::{
+    AuditLogRepository, AuditLogRepositoryError, AuditLogStream,
+};
+use crate::http::client_ip::{ClientIpResolver, ForwardedHeader};
+use crate::http::concurrency::{ConcurrencyConfig, ConcurrencyLimiter};
+use crate::http::ip_access::IpAccessList;
+use crate::http::middleware::rate_limit_policy::RateLimitPolicy;
+use crate::infrastructure::persistence::postgres_password_history_repository::PostgresPasswordHistoryRepository;
+use crate::infrastructure::persistence::postgres_refresh_token_repository::PostgresRefreshTokenRepository;
+use crate::infrastructure::persistence::postgres_user_repository::PostgresUserRepository;
+use crate::infrastructure::rate_limit::in_memory_ban_store::InMemoryBanStore;
+use crate::infrastructure::rate_limit::in_memory_store::InMemoryRateLimitStore;
+use crate::infrastructure::security::argon2_hasher::{Argon2Config, Argon2PasswordHasher};
+use crate::infrastructure::security::blocking_pool::BlockingPool;
+use crate::infrastructure::security::jwt_service::JwtServiceImpl;
+use crate::infrastructure::security::legacy_hasher::MultiAlgorithmPasswordHasher;
+use crate::shared::config::{AppConfig, RedisTopology};
+use crate::shared::metrics::Metrics;
+use crate::shared::state::AppState;
+
+/// Peer address `send` uses when the request does not set one.
+pub const PEER: &str = "192.0.2.10:40000";
+
+pub fn config() -> AppConfig {
+    AppConfig {
+        app_name: "api".into(),
+        env: "test".into(),
+        http_addr: "127.0.0.1:0".parse().unwrap(),
+        jwt_secret: "test secret".into(),
+        database_url: "postgres://localhost/unused".into(),
+        jwt_ttl_seconds: 900,
+        refresh_token_ttl_seconds: 3600,
+        redis_topology: RedisTopology::Standalone {
+            url: "redis://127.0.0.1:6379".into(),
+        },
+        use_redis_rate_limit: false,
+        rate_limit_local_fallback: false,
+        rate_limit_max_entries: 1024,
+        rate_limit_shards: 1,
+        rate_limit_sweep_interval: Duration::from_secs(60),
+        rate_limit_circuit_failure_threshold: 5,
+        rate_limit_circuit_open_duration: Duration::from_secs(30),
+        rate_limit_redis_timeout: Duration::from_millis(100),
+        rate_limit_config_path: None,
+        trusted_proxies: Vec::new(),
+        trusted_proxy_header: ForwardedHeader::default(),
+        ip_allowlist: Vec::new(),
+        ip_denylist: Vec::new(),
+        auto_ban: AutoBanConfig {
+            threshold: 0,
+            window: Duration::from_secs(60),
+            duration: Duration::from_secs(600),
+        },
+        login_throttle: LoginThrottleConfig {
+            account_limit: 5,
+            strict_account_limit: 2,
+            account_window: Duration::from_secs(60),
+            failure_threshold: 100,
+            failure_window: Duration::from_secs(60),
+            strict_cooldown: Duration::from_secs(60),
+        },
+        concurrency: ConcurrencyConfig {
+            max_in_flight: 64,
+            max_queued: 64,
+            queue_timeout: Duration::from_secs(1),
+            retry_after: Duration::from_secs(1),
+            routes: Vec::new(),
+        },
+        password_hash_concurrency: 2,
+        argon2: Argon2Config {
+            memory_kib: 1024,
+            iterations: 1,
+            parallelism: 1,
+            ..Argon2Config::default()
+        },
+        password_policy: PasswordPolicyConfig {
+            min_length: 8,
+            max_length: 128,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            min_entropy_bits: 0.0,
+            common_list_path: None,
+        },
+        hibp_filter_path: None,
+        password_history_size: 0,
+        password_max_age: None,
+        problem_type_base: "/problems".into(),
+        access_log_slow_threshold: Duration::from_secs(60),
+    }
+}
+
+/// State wired the way `main` wires it, from `config` and `policy`.
+pub fn state_with(config: AppConfig, policy: RateLimitPolicy) -> AppState {
+    let db = PgPoolOptions::new()
+        .connect_lazy(&config.database_url)
+        .expect("valid database url");
+
+    let metrics = Arc::new(Metrics::new());
+    let rate_limit_store = Arc::new(InMemoryRateLimitStore::new(
+        config.rate_limit_max_entries,
+        config.rate_limit_shards,
+        metrics.clone(),
+    ));
+    let ban_store = Arc::new(InMemoryBanStore::new());
+
+    let audit_log_repo = Arc::new(RecordedAuditLogs::default());
+    let audit_logger = Arc::new(AuditLogger::new(audit_log_repo.clone()));
+
+    let pool = BlockingPool::new(config.password_hash_concurrency);
+    let argon2 = Argon2PasswordHasher::new(&config.argon2, pool.clone()).unwrap();
+
+    AppState {
+        user_repo: Arc::new(PostgresUserRepository::new(db.clone())),
+        refresh_token_repo: Arc::new(PostgresRefreshTokenRepository::new(db.clone())),
+        audit_log_repo,
+        password_history_repo: Arc::new(PostgresPasswordHistoryRepository::new(db)),
+        password_hasher: Arc::new(MultiAlgorithmPasswordHasher::new(argon2, pool)),
+        jwt_service: Arc::new(JwtServiceImpl::new(&config.jwt_secret, config.jwt_ttl_seconds)),
+        password_policy: Arc::new(PasswordPolicy::load(config.password_policy.clone()).unwrap()),
+        login_throttle: Arc::new(LoginThrottle::new(
+            rate_limit_store.clone(),
+            config.login_throttle.clone(),
+        )),
+        auto_ban: Arc::new(AutoBan::new(
+            rate_limit_store.clone(),
+            ban_store.clone(),
+            audit_logger.clone(),
+            config.auto_ban.clone(),
+        )),
+        audit_logger,
+        rate_limit_store,
+        rate_limit_policy: Arc::new(policy),
+        client_ip_resolver: Arc::new(ClientIpResolver::new(
+            config.trusted_proxies.clone(),
+            config.trusted_proxy_header,
+        )),
+        ip_access: Arc::new(IpAccessList::new(
+            config.ip_allowlist.clone(),
+            config.ip_denylist.clone(),
+        )),
+        ban_store,
+        concurrency_limiter: Arc::new(ConcurrencyLimiter::new(&config.concurrency)),
+        metrics,
+        config,
+    }
+}
+
+/// Test config and the bundled rate limit rules.
+pub fn state() -> AppState {
+    state_with(config(), RateLimitPolicy::load(None).unwrap())
+}
+
+/// Sends `req` through `app` as if it came from `PEER`, unless the request
+/// already carries a `ConnectInfo`.
+pub async fn send(app: &Router, mut req: Request<Body>) -> Response {
+    if req.extensions().get::<ConnectInfo<SocketAddr>>().is_none() {
+        req.extensions_mut()
+            .insert(ConnectInfo(PEER.parse::<SocketAddr>().unwrap()));
+    }
+
+    app.clone().oneshot(req).await.unwrap()
+}
+
+pub async fn json_body(response: Response) -> serde_json::Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+/// Keeps every stored entry for inspection; queries find nothing.
+#[derive(Default)]
+pub struct RecordedAuditLogs {
+    pub logs: Mutex<Vec<AuditLog>>,
+}
+
+#[async_trait]
+impl AuditLogRepository for RecordedAuditLogs {
+    async fn store(&self, log: AuditLog) {
+        self.logs.lock().unwrap().push(log);
+    }
+
+    async fn find(
+        &self,
+        _filter: &AuditLogFilter,
+        _limit: u32,
+        _offset: u64,
+    ) -> Result<Vec<AuditLog>, AuditLogRepositoryError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _filter: &AuditLogFilter) -> Result<u64, AuditLogRepositoryError> {
+        Ok(0)
+    }
+
+    async fn metadata_keys(
+        &self,
+        _filter: &AuditLogFilter,
+    ) -> Result<Vec<String>, AuditLogRepositoryError> {
+        Ok(Vec::new())
+    }
+
+    fn stream(&self, _filter: AuditLogFilter) -> AuditLogStream {
+        stream::empty().boxed()
+    }
+}
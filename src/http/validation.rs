@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use crate::application::security::password_policy::PasswordViolation;
+use crate::domain::user::errors::UserDomainError;
+use crate::http::error::ApiError;
+use crate::shared::state::AppState;
+use crate::shared::{api_codes, api_messages};
+
+/// Checks a deserialized request body before the handler sees it. Runs
+/// inside `ValidatedJson`, so every rule reports at once instead of the
+/// handler stopping at the first failure.
+pub trait Validate {
+    fn validate(&self, state: &AppState) -> Result<(), ValidationErrors>;
+}
+
+/// Messages per request field, in the order they were found.
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    fields: HashMap<String, Vec<String>>,
+    /// Reported as `WEAK_PASSWORD` rather than a generic validation error,
+    /// wherever the password was checked.
+    weak_password: bool,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.fields
+            .entry(field.to_string())
+            .or_default()
+            .push(message.into());
+    }
+
+    /// Every violation becomes its own message under `field`.
+    pub fn weak_password(&mut self, field: &str, violations: &[PasswordViolation]) {
+        for violation in violations {
+            self.add(field, violation.to_string());
+        }
+
+        self.weak_password |= !violations.is_empty();
+    }
+
+    pub fn require(&mut self, field: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.add(field, api_messages::validator::REQUIRED);
+        }
+    }
+
+    /// Records a value object's error under the field it came from.
+    pub fn check<T>(&mut self, result: Result<T, UserDomainError>) -> Option<T> {
+        result
+            .map_err(|e| self.add(user_error_field(&e), e.to_string()))
+            .ok()
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.fields.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        let (code, message) = if errors.weak_password {
+            (api_codes::users::WEAK_PASSWORD, api_messages::users::WEAK_PASSWORD)
+        } else {
+            (
+                api_codes::validator::VALIDATION_ERROR,
+                api_messages::validator::INVALID_FIELDS,
+            )
+        };
+
+        ApiError::Validation {
+            code,
+            message,
+            errors: errors.fields,
+        }
+    }
+}
+
+fn user_error_field(error: &UserDomainError) -> &'static str {
+    match error {
+        UserDomainError::InvalidEmail => "email",
+        UserDomainError::InvalidName => "name",
+        UserDomainError::UnknownRole => "role",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(error: ApiError) -> &'static str {
+        match error {
+            ApiError::Validation { code, .. } => code,
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn weak_passwords_report_the_same_code_from_every_route() {
+        let violations = [PasswordViolation::TooShort(8), PasswordViolation::MissingUppercase];
+
+        // Register validates the body alongside its other fields.
+        let mut errors = ValidationErrors::new();
+        errors.add("email", "invalid email");
+        errors.weak_password("password", &violations);
+
+        // Change-password can only check once the user is loaded.
+        let change_password = ApiError::weak_password("new_password", &violations);
+
+        assert_eq!(code(errors.into()), api_codes::users::WEAK_PASSWORD);
+        assert_eq!(code(change_password), api_codes::users::WEAK_PASSWORD);
+    }
+
+    #[test]
+    fn other_field_errors_stay_generic() {
+        let mut errors = ValidationErrors::new();
+        errors.require("name", " ");
+
+        assert_eq!(code(errors.into()), api_codes::validator::VALIDATION_ERROR);
+    }
+}
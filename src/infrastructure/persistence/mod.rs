@@ -1,3 +1,4 @@
 pub mod postgres_user_repository;
 pub mod postgres_refresh_token_repository;
-pub mod postgres_audit_log_repository;
\ No newline at end of file
+pub mod postgres_audit_log_repository;
+pub mod postgres_password_history_repository;
@@ -1,6 +1,17 @@
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use sqlx::{postgres::PgRow, PgPool, Postgres, QueryBuilder, Row};
+use tokio::sync::mpsc;
+use uuid::Uuid;
 
-use crate::domain::audit::{entity::AuditLog, repository::AuditLogRepository};
+use crate::domain::audit::{
+    entity::AuditLog,
+    filter::AuditLogFilter,
+    repository::{AuditLogRepository, AuditLogRepositoryError, AuditLogStream},
+};
+
+/// Rows buffered between the export query and the response body.
+const STREAM_BUFFER: usize = 256;
 
 pub struct PostgresAuditLogRepository {
     pool: PgPool,
@@ -12,6 +23,50 @@ impl PostgresAuditLogRepository {
     }
 }
 
+fn push_filter(builder: &mut QueryBuilder<Postgres>, filter: &AuditLogFilter) {
+    builder.push(" WHERE TRUE");
+
+    if let Some(actor_id) = filter.actor_id {
+        builder.push(" AND actor_id = ").push_bind(actor_id);
+    }
+
+    if let Some(action) = &filter.action {
+        builder.push(" AND action = ").push_bind(action.clone());
+    }
+
+    if let Some(resource) = &filter.resource {
+        builder.push(" AND resource = ").push_bind(resource.clone());
+    }
+
+    if let Some(from) = filter.from {
+        builder.push(" AND created_at >= ").push_bind(from);
+    }
+
+    if let Some(to) = filter.to {
+        builder.push(" AND created_at < ").push_bind(to);
+    }
+}
+
+fn select_query(filter: &AuditLogFilter) -> QueryBuilder<Postgres> {
+    let mut builder = QueryBuilder::new(
+        "SELECT id, actor_id, action, resource, metadata, created_at FROM audit_logs",
+    );
+    push_filter(&mut builder, filter);
+    builder.push(" ORDER BY created_at, id");
+    builder
+}
+
+fn map_row(row: &PgRow) -> AuditLog {
+    AuditLog {
+        id: row.get::<Uuid, _>("id"),
+        actor_id: row.get::<Option<Uuid>, _>("actor_id"),
+        action: row.get::<String, _>("action"),
+        resource: row.get::<String, _>("resource"),
+        metadata: row.get::<serde_json::Value, _>("metadata"),
+        created_at: row.get::<DateTime<Utc>, _>("created_at"),
+    }
+}
+
 #[async_trait::async_trait]
 impl AuditLogRepository for PostgresAuditLogRepository {
     async fn store(&self, log: AuditLog) {
@@ -31,4 +86,89 @@ impl AuditLogRepository for PostgresAuditLogRepository {
             .await;
         // deliberately ignored
     }
+
+    async fn find(
+        &self,
+        filter: &AuditLogFilter,
+        limit: u32,
+        offset: u64,
+    ) -> Result<Vec<AuditLog>, AuditLogRepositoryError> {
+        let mut builder = select_query(filter);
+        builder
+            .push(" LIMIT ")
+            .push_bind(i64::from(limit))
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let rows = builder
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|_| AuditLogRepositoryError::Unexpected)?;
+
+        Ok(rows.iter().map(map_row).collect())
+    }
+
+    async fn count(&self, filter: &AuditLogFilter) -> Result<u64, AuditLogRepositoryError> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) AS total FROM audit_logs");
+        push_filter(&mut builder, filter);
+
+        let row = builder
+            .build()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|_| AuditLogRepositoryError::Unexpected)?;
+
+        Ok(row.get::<i64, _>("total") as u64)
+    }
+
+    async fn metadata_keys(
+        &self,
+        filter: &AuditLogFilter,
+    ) -> Result<Vec<String>, AuditLogRepositoryError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT DISTINCT jsonb_object_keys(metadata) AS key FROM audit_logs",
+        );
+        push_filter(&mut builder, filter);
+        builder.push(" AND jsonb_typeof(metadata) = 'object' ORDER BY key");
+
+        let rows = builder
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|_| AuditLogRepositoryError::Unexpected)?;
+
+        Ok(rows.iter().map(|row| row.get::<String, _>("key")).collect())
+    }
+
+    fn stream(&self, filter: AuditLogFilter) -> AuditLogStream {
+        let pool = self.pool.clone();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+
+        // The sqlx row stream borrows the query and pool, so it is driven from
+        // its own task. The bounded channel applies backpressure, and dropping
+        // the receiver (client disconnect) ends the query.
+        tokio::spawn(async move {
+            let mut builder = select_query(&filter);
+            let mut rows = builder.build().fetch(&pool);
+
+            loop {
+                let item = match rows.try_next().await {
+                    Ok(Some(row)) => Ok(map_row(&row)),
+                    Ok(None) => break,
+                    Err(_) => Err(AuditLogRepositoryError::Unexpected),
+                };
+                let failed = item.is_err();
+
+                if tx.send(item).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        })
+        .boxed()
+    }
 }
@@ -0,0 +1,45 @@
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::user::password_history::{
+    PasswordHistoryRepository, PasswordHistoryRepositoryError,
+};
+
+pub struct PostgresPasswordHistoryRepository {
+    pool: PgPool,
+}
+
+impl PostgresPasswordHistoryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PasswordHistoryRepository for PostgresPasswordHistoryRepository {
+    async fn recent(
+        &self,
+        user_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<String>, PasswordHistoryRepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT password_hash
+            FROM password_history
+            WHERE user_id = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|_| PasswordHistoryRepositoryError::Unexpected)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| row.get::<String, _>("password_hash"))
+            .collect())
+    }
+}
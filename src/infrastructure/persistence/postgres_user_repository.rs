@@ -5,7 +5,7 @@ use uuid::Uuid;
 use crate::domain::user::{
     entity::User,
     repository::{UserRepository, UserRepositoryError},
-    value_objects::{UserEmail, UserName},
+    value_objects::{UserEmail, UserName, UserRole},
 };
 
 pub struct PostgresUserRepository {
@@ -23,7 +23,7 @@ impl UserRepository for PostgresUserRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<User, UserRepositoryError> {
         let row = sqlx::query(
             r#"
-            SELECT id, name, email, password_hash
+            SELECT id, name, email, password_hash, role, password_changed_at, must_change_password
             FROM users
             WHERE id = $1
             "#,
@@ -44,18 +44,24 @@ impl UserRepository for PostgresUserRepository {
         let email = UserEmail::new(row.get::<String, _>("email"))
             .map_err(|_| UserRepositoryError::Domain)?;
 
-        Ok(User::register(
+        let role = UserRole::parse(&row.get::<String, _>("role"))
+            .map_err(|_| UserRepositoryError::Domain)?;
+
+        Ok(User::restore(
             row.get("id"),
             name,
             email,
             row.get("password_hash"),
+            role,
+            row.get("password_changed_at"),
+            row.get("must_change_password"),
         ))
     }
 
     async fn find_by_email(&self, email: &str) -> Result<User, UserRepositoryError> {
         let row = sqlx::query(
             r#"
-            SELECT id, name, email, password_hash
+            SELECT id, name, email, password_hash, role, password_changed_at, must_change_password
             FROM users
             WHERE email = $1
             "#,
@@ -76,25 +82,34 @@ impl UserRepository for PostgresUserRepository {
         let email = UserEmail::new(row.get::<String, _>("email"))
             .map_err(|_| UserRepositoryError::Domain)?;
 
-        Ok(User::register(
+        let role = UserRole::parse(&row.get::<String, _>("role"))
+            .map_err(|_| UserRepositoryError::Domain)?;
+
+        Ok(User::restore(
             row.get("id"),
             name,
             email,
             row.get("password_hash"),
+            role,
+            row.get("password_changed_at"),
+            row.get("must_change_password"),
         ))
     }
 
     async fn save(&self, user: &User) -> Result<(), UserRepositoryError> {
         let result = sqlx::query(
             r#"
-            INSERT INTO users (id, name, email, password_hash)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO users (id, name, email, password_hash, role, password_changed_at, must_change_password)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
         .bind(user.id())
         .bind(user.name().value())
         .bind(user.email().value())
         .bind(user.password_hash())
+        .bind(user.role().as_str())
+        .bind(user.password_changed_at())
+        .bind(user.must_change_password())
         .execute(&self.pool)
         .await;
 
@@ -102,10 +117,10 @@ impl UserRepository for PostgresUserRepository {
             Ok(_) => Ok(()),
             Err(e) => {
                 // unique violation (email)
-                if let Some(db_err) = e.as_database_error() {
-                    if db_err.code() == Some(std::borrow::Cow::Borrowed("23505")) {
-                        return Err(UserRepositoryError::Conflict);
-                    }
+                if let Some(db_err) = e.as_database_error()
+                    && db_err.code() == Some(std::borrow::Cow::Borrowed("23505"))
+                {
+                    return Err(UserRepositoryError::Conflict);
                 }
 
                 Err(UserRepositoryError::Unknown)
@@ -118,13 +133,17 @@ impl UserRepository for PostgresUserRepository {
             r#"
         UPDATE users
         SET name = $2,
-        password_hash = $3
+        password_hash = $3,
+        password_changed_at = $4,
+        must_change_password = $5
         WHERE id = $1
         "#,
         )
         .bind(user.id())
         .bind(user.name().value())
         .bind(user.password_hash())
+        .bind(user.password_changed_at())
+        .bind(user.must_change_password())
         .execute(&self.pool)
         .await
         .map_err(|_| UserRepositoryError::Unknown)?;
@@ -135,4 +154,76 @@ impl UserRepository for PostgresUserRepository {
 
         Ok(())
     }
+
+    async fn update_password(
+        &self,
+        user: &User,
+        previous_hash: &str,
+        keep_history: usize,
+    ) -> Result<(), UserRepositoryError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|_| UserRepositoryError::Unknown)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET password_hash = $2,
+                password_changed_at = $3,
+                must_change_password = $4
+            WHERE id = $1
+            "#,
+        )
+        .bind(user.id())
+        .bind(user.password_hash())
+        .bind(user.password_changed_at())
+        .bind(user.must_change_password())
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| UserRepositoryError::Unknown)?;
+
+        if result.rows_affected() == 0 {
+            return Err(UserRepositoryError::NotFound);
+        }
+
+        if keep_history > 0 {
+            sqlx::query(
+                r#"
+                INSERT INTO password_history (id, user_id, password_hash)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(Uuid::now_v7())
+            .bind(user.id())
+            .bind(previous_hash)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| UserRepositoryError::Unknown)?;
+
+            sqlx::query(
+                r#"
+                DELETE FROM password_history
+                WHERE user_id = $1
+                    AND id NOT IN (
+                        SELECT id
+                        FROM password_history
+                        WHERE user_id = $1
+                        ORDER BY created_at DESC, id DESC
+                        LIMIT $2
+                    )
+                "#,
+            )
+            .bind(user.id())
+            .bind(keep_history as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| UserRepositoryError::Unknown)?;
+        }
+
+        tx.commit().await.map_err(|_| UserRepositoryError::Unknown)?;
+
+        Ok(())
+    }
 }
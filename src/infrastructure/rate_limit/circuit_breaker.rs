@@ -0,0 +1,161 @@
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::shared::metrics::Metrics;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+
+    /// Value exported by the `rate_limit_circuit_state` gauge.
+    pub fn as_gauge(&self) -> i64 {
+        match self {
+            CircuitState::Closed => 0,
+            CircuitState::Open => 1,
+            CircuitState::HalfOpen => 2,
+        }
+    }
+}
+
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// Start of the outstanding half-open probe. A probe whose request was
+    /// dropped never reports back, so it only blocks others for one
+    /// `open_duration`.
+    probe_started_at: Option<Instant>,
+}
+
+/// Trips after `failure_threshold` consecutive failures and stays open for
+/// `open_duration`. It then lets a single probe through; success closes the
+/// circuit and failure opens it again.
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    inner: Mutex<Inner>,
+}
+
+/// A state change, returned so the caller can log and export it.
+pub type Transition = (CircuitState, CircuitState);
+
+/// Logs a state change of the Redis circuit and exports it.
+pub fn report(metrics: &Metrics, transition: Option<Transition>) {
+    let Some((from, to)) = transition else {
+        return;
+    };
+
+    tracing::warn!(
+        from = from.as_str(),
+        to = to.as_str(),
+        "rate limit store circuit changed state"
+    );
+
+    metrics
+        .rate_limit_circuit_state
+        .store(to.as_gauge(), Ordering::Relaxed);
+    metrics
+        .rate_limit_circuit_transitions
+        .fetch_add(1, Ordering::Relaxed);
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started_at: None,
+            }),
+        }
+    }
+
+    /// Whether the protected call should be attempted.
+    pub fn try_acquire(&self) -> (bool, Option<Transition>) {
+        let mut inner = self.inner.lock().unwrap();
+
+        match inner.state {
+            CircuitState::Closed => (true, None),
+            CircuitState::Open => {
+                let elapsed = inner.opened_at.is_some_and(|at| at.elapsed() >= self.open_duration);
+
+                if !elapsed {
+                    return (false, None);
+                }
+
+                inner.state = CircuitState::HalfOpen;
+                inner.probe_started_at = Some(Instant::now());
+
+                (true, Some((CircuitState::Open, CircuitState::HalfOpen)))
+            }
+            CircuitState::HalfOpen => {
+                let outstanding = inner
+                    .probe_started_at
+                    .is_some_and(|at| at.elapsed() < self.open_duration);
+
+                if outstanding {
+                    return (false, None);
+                }
+
+                inner.probe_started_at = Some(Instant::now());
+                (true, None)
+            }
+        }
+    }
+
+    pub fn on_success(&self) -> Option<Transition> {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.consecutive_failures = 0;
+        inner.probe_started_at = None;
+
+        let previous = inner.state;
+        if previous == CircuitState::Closed {
+            return None;
+        }
+
+        inner.state = CircuitState::Closed;
+        inner.opened_at = None;
+
+        Some((previous, CircuitState::Closed))
+    }
+
+    pub fn on_failure(&self) -> Option<Transition> {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        inner.probe_started_at = None;
+
+        let previous = inner.state;
+        let trips = match previous {
+            CircuitState::Closed => inner.consecutive_failures >= self.failure_threshold,
+            CircuitState::HalfOpen => true,
+            CircuitState::Open => false,
+        };
+
+        if !trips {
+            return None;
+        }
+
+        inner.state = CircuitState::Open;
+        inner.opened_at = Some(Instant::now());
+
+        Some((previous, CircuitState::Open))
+    }
+}
@@ -0,0 +1,226 @@
+//! Scenarios every `RateLimitStore` must pass for every algorithm. Each one
+//! runs against the in-memory store and against the Redis store. The Redis
+//! cases need a server at `REDIS_URL` (default `redis://127.0.0.1:6379`), so
+//! they are `#[ignore]`d; CI runs them with `--include-ignored`, and they
+//! fail rather than pass when Redis cannot be reached.
+
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::rate_limit::algorithm::RateLimitAlgorithm;
+use crate::domain::rate_limit::decision::RateLimitDecision;
+use crate::domain::rate_limit::store::RateLimitStore;
+use crate::infrastructure::rate_limit::in_memory_store::InMemoryRateLimitStore;
+use crate::infrastructure::rate_limit::redis_store::RedisRateLimitStore;
+use crate::shared::metrics::Metrics;
+
+/// Slack for timer granularity and Redis round trips.
+const MARGIN: Duration = Duration::from_millis(50);
+
+async fn in_memory_store() -> Arc<dyn RateLimitStore> {
+    Arc::new(InMemoryRateLimitStore::new(
+        1024,
+        4,
+        Arc::new(Metrics::new()),
+    ))
+}
+
+async fn redis_store() -> Arc<dyn RateLimitStore> {
+    let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
+
+    let connect = async {
+        let conn = ::redis::Client::open(url.as_str())?
+            .get_multiplexed_async_connection()
+            .await?;
+        RedisRateLimitStore::new(conn).await
+    };
+
+    match tokio::time::timeout(Duration::from_secs(2), connect).await {
+        Ok(Ok(store)) => Arc::new(store),
+        Ok(Err(e)) => panic!("Redis conformance tests need Redis at {url}: {e}"),
+        Err(_) => panic!("Redis conformance tests need Redis at {url}: connection timed out"),
+    }
+}
+
+/// Keys are unique per run so Redis state never leaks between cases.
+fn fresh_key() -> String {
+    format!("conformance:{}", Uuid::now_v7())
+}
+
+async fn check(
+    store: &dyn RateLimitStore,
+    key: &str,
+    limit: u32,
+    window: Duration,
+    algorithm: RateLimitAlgorithm,
+) -> RateLimitDecision {
+    store
+        .check(key.to_string(), limit, window, algorithm)
+        .await
+        .expect("rate limit check failed")
+}
+
+async fn exhaust(
+    store: &dyn RateLimitStore,
+    key: &str,
+    limit: u32,
+    window: Duration,
+    algorithm: RateLimitAlgorithm,
+) -> RateLimitDecision {
+    for _ in 0..limit {
+        assert!(check(store, key, limit, window, algorithm).await.allowed);
+    }
+
+    let denied = check(store, key, limit, window, algorithm).await;
+    assert!(!denied.allowed, "request over the limit was allowed");
+
+    denied
+}
+
+async fn limit_boundary(store: Arc<dyn RateLimitStore>, algorithm: RateLimitAlgorithm) {
+    let key = fresh_key();
+    let limit = 3;
+    let window = Duration::from_secs(60);
+
+    for used in 1..=limit {
+        let decision = check(&*store, &key, limit, window, algorithm).await;
+
+        assert!(decision.allowed, "request {used} of {limit} was rejected");
+        assert_eq!(decision.limit, limit);
+        assert_eq!(decision.remaining, limit - used);
+        assert!(decision.reset_after <= window * 2);
+    }
+
+    let denied = check(&*store, &key, limit, window, algorithm).await;
+
+    assert!(!denied.allowed);
+    assert_eq!(denied.remaining, 0);
+
+    let retry_after = denied.retry_after.expect("denied decision without retry_after");
+    assert!(retry_after > Duration::ZERO);
+    assert!(retry_after <= window * 2);
+}
+
+async fn window_rollover(store: Arc<dyn RateLimitStore>, algorithm: RateLimitAlgorithm) {
+    let key = fresh_key();
+    let limit = 2;
+    let window = Duration::from_millis(300);
+
+    exhaust(&*store, &key, limit, window, algorithm).await;
+
+    // Two full windows: long enough for the sliding window's previous
+    // counter to stop weighing too.
+    tokio::time::sleep(window * 2 + MARGIN).await;
+
+    for used in 1..=limit {
+        let decision = check(&*store, &key, limit, window, algorithm).await;
+
+        assert!(decision.allowed, "request {used} after rollover was rejected");
+        assert_eq!(decision.remaining, limit - used);
+    }
+}
+
+async fn retry_after_is_honoured(store: Arc<dyn RateLimitStore>, algorithm: RateLimitAlgorithm) {
+    let key = fresh_key();
+    let limit = 2;
+    let window = Duration::from_millis(400);
+
+    let denied = exhaust(&*store, &key, limit, window, algorithm).await;
+    let retry_after = denied.retry_after.expect("denied decision without retry_after");
+
+    tokio::time::sleep(retry_after + MARGIN).await;
+
+    let decision = check(&*store, &key, limit, window, algorithm).await;
+    assert!(decision.allowed, "rejected after waiting retry_after ({retry_after:?})");
+}
+
+async fn reset_restores_quota(store: Arc<dyn RateLimitStore>, algorithm: RateLimitAlgorithm) {
+    let key = fresh_key();
+    let limit = 2;
+    let window = Duration::from_secs(60);
+
+    exhaust(&*store, &key, limit, window, algorithm).await;
+
+    store
+        .reset(key.clone(), algorithm)
+        .await
+        .expect("rate limit reset failed");
+
+    let decision = check(&*store, &key, limit, window, algorithm).await;
+    assert!(decision.allowed, "rejected after reset");
+    assert_eq!(decision.remaining, limit - 1);
+}
+
+async fn token_bucket_refill(store: Arc<dyn RateLimitStore>) {
+    let key = fresh_key();
+    let limit = 4;
+    let window = Duration::from_millis(400);
+    let algorithm = RateLimitAlgorithm::TokenBucket;
+
+    exhaust(&*store, &key, limit, window, algorithm).await;
+
+    // One and a half tokens' worth of refill.
+    tokio::time::sleep(window / limit * 3 / 2).await;
+
+    assert!(check(&*store, &key, limit, window, algorithm).await.allowed);
+    assert!(!check(&*store, &key, limit, window, algorithm).await.allowed);
+}
+
+macro_rules! conformance_test {
+    ([$(#[$attr:meta])*] $name:ident: $body:expr) => {
+        #[tokio::test]
+        $(#[$attr])*
+        async fn $name() {
+            $body.await;
+        }
+    };
+}
+
+macro_rules! conformance_suite {
+    ($attrs:tt $store:ident, $connect:ident: $($scenario:ident),+ $(,)?) => {
+        mod $store {
+            $(
+                mod $scenario {
+                    use crate::domain::rate_limit::algorithm::RateLimitAlgorithm;
+
+                    conformance_test!($attrs fixed_window: async {
+                        let store = super::super::$connect().await;
+                        super::super::$scenario(store, RateLimitAlgorithm::FixedWindow).await;
+                    });
+
+                    conformance_test!($attrs sliding_window: async {
+                        let store = super::super::$connect().await;
+                        super::super::$scenario(store, RateLimitAlgorithm::SlidingWindow).await;
+                    });
+
+                    conformance_test!($attrs token_bucket: async {
+                        let store = super::super::$connect().await;
+                        super::super::$scenario(store, RateLimitAlgorithm::TokenBucket).await;
+                    });
+                }
+            )+
+
+            conformance_test!($attrs token_bucket_refill: async {
+                super::token_bucket_refill(super::$connect().await).await;
+            });
+        }
+    };
+}
+
+conformance_suite!(
+    []
+    in_memory,
+    in_memory_store: limit_boundary,
+    window_rollover,
+    retry_after_is_honoured,
+    reset_restores_quota,
+);
+conformance_suite!(
+    [#[ignore = "needs Redis at REDIS_URL; run with --include-ignored"]]
+    redis,
+    redis_store: limit_boundary,
+    window_rollover,
+    retry_after_is_honoured,
+    reset_restores_quota,
+);
@@ -0,0 +1,215 @@
+use async_trait::async_trait;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::rate_limit::{
+    algorithm::RateLimitAlgorithm,
+    decision::RateLimitDecision,
+    store::{RateLimitError, RateLimitStore},
+};
+use crate::infrastructure::rate_limit::circuit_breaker::{self, CircuitBreaker, Transition};
+use crate::shared::metrics::Metrics;
+
+/// Sends checks to `primary` (Redis) behind a circuit breaker, so a failing
+/// primary is skipped instead of waited on. While it is failing or the
+/// circuit is open, checks either fail, leaving each rule's `fail_mode` to
+/// decide, or, when a `fallback` is configured, are answered from it and
+/// enforced per instance instead of globally.
+pub struct FailoverRateLimitStore {
+    primary: Arc<dyn RateLimitStore>,
+    fallback: Option<Arc<dyn RateLimitStore>>,
+    breaker: Arc<CircuitBreaker>,
+    timeout: Duration,
+    metrics: Arc<Metrics>,
+}
+
+impl FailoverRateLimitStore {
+    pub fn new(
+        primary: Arc<dyn RateLimitStore>,
+        fallback: Option<Arc<dyn RateLimitStore>>,
+        breaker: Arc<CircuitBreaker>,
+        timeout: Duration,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        Self {
+            primary,
+            fallback,
+            breaker,
+            timeout,
+            metrics,
+        }
+    }
+
+    fn record(&self, transition: Option<Transition>) {
+        circuit_breaker::report(&self.metrics, transition);
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for FailoverRateLimitStore {
+    async fn check(
+        &self,
+        key: String,
+        limit: u32,
+        window: Duration,
+        algorithm: RateLimitAlgorithm,
+    ) -> Result<RateLimitDecision, RateLimitError> {
+        let (attempt, transition) = self.breaker.try_acquire();
+        self.record(transition);
+
+        if attempt {
+            // A hung connection counts as a failure, not as a stalled request.
+            let result = tokio::time::timeout(
+                self.timeout,
+                self.primary.check(key.clone(), limit, window, algorithm),
+            )
+            .await;
+
+            match result {
+                Ok(Ok(decision)) => {
+                    self.record(self.breaker.on_success());
+                    return Ok(decision);
+                }
+                Ok(Err(_)) | Err(_) => {
+                    tracing::warn!("rate limit store check failed");
+                    self.record(self.breaker.on_failure());
+                }
+            }
+        }
+
+        let Some(fallback) = &self.fallback else {
+            return Err(RateLimitError::StorageError);
+        };
+
+        self.metrics
+            .rate_limit_fallback_checks
+            .fetch_add(1, Ordering::Relaxed);
+
+        fallback.check(key, limit, window, algorithm).await
+    }
+
+    /// Clears the key in both stores, since either may have counted it.
+    /// Like `check`, an unavailable primary is only an error without a
+    /// fallback; with one, the reset applies to the counts it is enforcing.
+    async fn reset(&self, key: String, algorithm: RateLimitAlgorithm) -> Result<(), RateLimitError> {
+        if let Some(fallback) = &self.fallback {
+            fallback.reset(key.clone(), algorithm).await?;
+        }
+
+        let unavailable = || match self.fallback {
+            Some(_) => Ok(()),
+            None => Err(RateLimitError::StorageError),
+        };
+
+        let (attempt, transition) = self.breaker.try_acquire();
+        self.record(transition);
+
+        if !attempt {
+            return unavailable();
+        }
+
+        let result = tokio::time::timeout(self.timeout, self.primary.reset(key, algorithm)).await;
+
+        match result {
+            Ok(Ok(())) => {
+                self.record(self.breaker.on_success());
+                Ok(())
+            }
+            Ok(Err(_)) | Err(_) => {
+                tracing::warn!("rate limit store reset failed");
+                self.record(self.breaker.on_failure());
+                unavailable()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::rate_limit::in_memory_store::InMemoryRateLimitStore;
+
+    struct Unavailable;
+
+    #[async_trait]
+    impl RateLimitStore for Unavailable {
+        async fn check(
+            &self,
+            _key: String,
+            _limit: u32,
+            _window: Duration,
+            _algorithm: RateLimitAlgorithm,
+        ) -> Result<RateLimitDecision, RateLimitError> {
+            Err(RateLimitError::StorageError)
+        }
+
+        async fn reset(&self, _key: String, _algorithm: RateLimitAlgorithm) -> Result<(), RateLimitError> {
+            Err(RateLimitError::StorageError)
+        }
+    }
+
+    fn store(fallback: Option<Arc<dyn RateLimitStore>>) -> FailoverRateLimitStore {
+        let metrics = Arc::new(Metrics::new());
+
+        FailoverRateLimitStore::new(
+            Arc::new(Unavailable),
+            fallback,
+            Arc::new(CircuitBreaker::new(1, Duration::from_secs(60))),
+            Duration::from_millis(100),
+            metrics,
+        )
+    }
+
+    async fn check(store: &FailoverRateLimitStore) -> Result<RateLimitDecision, RateLimitError> {
+        store
+            .check("key".into(), 1, Duration::from_secs(60), RateLimitAlgorithm::FixedWindow)
+            .await
+    }
+
+    #[tokio::test]
+    async fn failures_surface_without_a_fallback() {
+        let store = store(None);
+
+        // The first check trips the circuit, the second never reaches Redis.
+        assert!(check(&store).await.is_err());
+        assert!(check(&store).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn fallback_enforces_limits_locally() {
+        let local = InMemoryRateLimitStore::new(16, 1, Arc::new(Metrics::new()));
+        let store = store(Some(Arc::new(local)));
+
+        assert!(check(&store).await.unwrap().allowed);
+        assert!(!check(&store).await.unwrap().allowed);
+    }
+
+    #[tokio::test]
+    async fn reset_succeeds_through_the_fallback_while_the_circuit_is_open() {
+        let local = InMemoryRateLimitStore::new(16, 1, Arc::new(Metrics::new()));
+        let store = store(Some(Arc::new(local)));
+
+        // Trips the circuit and uses up the local quota.
+        assert!(check(&store).await.unwrap().allowed);
+        assert!(!check(&store).await.unwrap().allowed);
+
+        store
+            .reset("key".into(), RateLimitAlgorithm::FixedWindow)
+            .await
+            .unwrap();
+
+        assert!(check(&store).await.unwrap().allowed);
+    }
+
+    #[tokio::test]
+    async fn reset_fails_while_the_circuit_is_open_without_a_fallback() {
+        let store = store(None);
+        assert!(check(&store).await.is_err());
+
+        assert!(store
+            .reset("key".into(), RateLimitAlgorithm::FixedWindow)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use std::future::Future;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::rate_limit::ban::IpBan;
+use crate::domain::rate_limit::ban_store::{BanStore, BanStoreError};
+use crate::infrastructure::rate_limit::circuit_breaker::{self, CircuitBreaker};
+use crate::shared::metrics::Metrics;
+
+/// Puts the Redis ban store behind the rate limiter's circuit breaker and
+/// timeout. Every request looks up bans, so a hung Redis must fail fast
+/// rather than stall all traffic; callers treat the error as "not banned".
+pub struct GuardedBanStore {
+    inner: Arc<dyn BanStore>,
+    breaker: Arc<CircuitBreaker>,
+    timeout: Duration,
+    metrics: Arc<Metrics>,
+}
+
+impl GuardedBanStore {
+    pub fn new(
+        inner: Arc<dyn BanStore>,
+        breaker: Arc<CircuitBreaker>,
+        timeout: Duration,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        Self {
+            inner,
+            breaker,
+            timeout,
+            metrics,
+        }
+    }
+
+    async fn guard<T>(
+        &self,
+        call: impl Future<Output = Result<T, BanStoreError>>,
+    ) -> Result<T, BanStoreError> {
+        let (attempt, transition) = self.breaker.try_acquire();
+        circuit_breaker::report(&self.metrics, transition);
+
+        if !attempt {
+            return Err(BanStoreError::StorageError);
+        }
+
+        match tokio::time::timeout(self.timeout, call).await {
+            Ok(Ok(value)) => {
+                circuit_breaker::report(&self.metrics, self.breaker.on_success());
+                Ok(value)
+            }
+            Ok(Err(_)) | Err(_) => {
+                circuit_breaker::report(&self.metrics, self.breaker.on_failure());
+                Err(BanStoreError::StorageError)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl BanStore for GuardedBanStore {
+    async fn ban(&self, ban: IpBan) -> Result<(), BanStoreError> {
+        self.guard(self.inner.ban(ban)).await
+    }
+
+    async fn find(&self, ip: IpAddr) -> Result<Option<IpBan>, BanStoreError> {
+        self.guard(self.inner.find(ip)).await
+    }
+
+    async fn list(&self) -> Result<Vec<IpBan>, BanStoreError> {
+        self.guard(self.inner.list()).await
+    }
+
+    async fn lift(&self, ip: IpAddr) -> Result<bool, BanStoreError> {
+        self.guard(self.inner.lift(ip)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Stands in for a Redis that accepts connections but never answers.
+    struct Hung;
+
+    #[async_trait]
+    impl BanStore for Hung {
+        async fn ban(&self, _ban: IpBan) -> Result<(), BanStoreError> {
+            std::future::pending().await
+        }
+
+        async fn find(&self, _ip: IpAddr) -> Result<Option<IpBan>, BanStoreError> {
+            std::future::pending().await
+        }
+
+        async fn list(&self) -> Result<Vec<IpBan>, BanStoreError> {
+            std::future::pending().await
+        }
+
+        async fn lift(&self, _ip: IpAddr) -> Result<bool, BanStoreError> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn hung_store_fails_fast_and_then_trips_the_circuit() {
+        let store = GuardedBanStore::new(
+            Arc::new(Hung),
+            Arc::new(CircuitBreaker::new(1, Duration::from_secs(60))),
+            Duration::from_millis(50),
+            Arc::new(Metrics::new()),
+        );
+        let ip: IpAddr = "203.0.113.9".parse().unwrap();
+
+        assert!(store.find(ip).await.is_err());
+
+        let started = Instant::now();
+        assert!(store.find(ip).await.is_err());
+        assert!(started.elapsed() < Duration::from_millis(50));
+    }
+}
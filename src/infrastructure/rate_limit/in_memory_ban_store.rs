@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+use crate::domain::rate_limit::ban::IpBan;
+use crate::domain::rate_limit::ban_store::{BanStore, BanStoreError};
+
+pub struct InMemoryBanStore {
+    bans: Mutex<HashMap<IpAddr, IpBan>>,
+}
+
+impl InMemoryBanStore {
+    pub fn new() -> Self {
+        Self {
+            bans: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl BanStore for InMemoryBanStore {
+    async fn ban(&self, ban: IpBan) -> Result<(), BanStoreError> {
+        self.bans.lock().unwrap().insert(ban.ip, ban);
+        Ok(())
+    }
+
+    async fn find(&self, ip: IpAddr) -> Result<Option<IpBan>, BanStoreError> {
+        let mut bans = self.bans.lock().unwrap();
+
+        match bans.get(&ip) {
+            Some(ban) if ban.is_expired(Utc::now()) => {
+                bans.remove(&ip);
+                Ok(None)
+            }
+            Some(ban) => Ok(Some(ban.clone())),
+            None => Ok(None),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<IpBan>, BanStoreError> {
+        let mut bans = self.bans.lock().unwrap();
+        let now = Utc::now();
+
+        bans.retain(|_, ban| !ban.is_expired(now));
+
+        Ok(bans.values().cloned().collect())
+    }
+
+    async fn lift(&self, ip: IpAddr) -> Result<bool, BanStoreError> {
+        let removed = self.bans.lock().unwrap().remove(&ip);
+
+        Ok(removed.is_some_and(|ban| !ban.is_expired(Utc::now())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn ban(ip: &str, expires_in: Duration) -> IpBan {
+        let now = Utc::now();
+
+        IpBan {
+            ip: ip.parse().unwrap(),
+            reason: "test".to_string(),
+            banned_at: now,
+            expires_at: now + expires_in,
+        }
+    }
+
+    #[tokio::test]
+    async fn lift_removes_a_ban_and_reports_unknown_ips() {
+        let store = InMemoryBanStore::new();
+        let banned = ban("203.0.113.9", Duration::minutes(10));
+        store.ban(banned.clone()).await.unwrap();
+
+        assert!(store.lift(banned.ip).await.unwrap());
+        assert!(store.find(banned.ip).await.unwrap().is_none());
+        assert!(!store.lift(banned.ip).await.unwrap());
+        assert!(!store.lift("198.51.100.1".parse().unwrap()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn expired_bans_are_dropped() {
+        let store = InMemoryBanStore::new();
+        let active = ban("203.0.113.9", Duration::minutes(10));
+        let expired = ban("198.51.100.1", Duration::seconds(-1));
+
+        store.ban(active.clone()).await.unwrap();
+        store.ban(expired.clone()).await.unwrap();
+
+        assert!(store.find(expired.ip).await.unwrap().is_none());
+
+        let listed = store.list().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].ip, active.ip);
+
+        store.ban(expired.clone()).await.unwrap();
+        assert!(!store.lift(expired.ip).await.unwrap());
+    }
+}
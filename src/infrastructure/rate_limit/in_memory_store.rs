@@ -1,23 +1,136 @@
 use async_trait::async_trait;
-use std::collections::HashMap;
-use std::sync::Mutex;
-use std::time::Duration;
+use hashlink::LruCache;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
 
 use crate::domain::rate_limit::{
+    algorithm::RateLimitAlgorithm,
     bucket::RateLimitBucket,
+    decision::RateLimitDecision,
+    sliding_window::SlidingWindowCounter,
     store::{RateLimitError, RateLimitStore},
+    token_bucket::TokenBucket,
 };
+use crate::shared::metrics::Metrics;
 
+enum RateLimitState {
+    FixedWindow(RateLimitBucket),
+    SlidingWindow(SlidingWindowCounter),
+    TokenBucket(TokenBucket),
+}
+
+impl RateLimitState {
+    fn new(algorithm: RateLimitAlgorithm, limit: u32, window: Duration) -> Self {
+        match algorithm {
+            RateLimitAlgorithm::FixedWindow => Self::FixedWindow(RateLimitBucket::new(window)),
+            RateLimitAlgorithm::SlidingWindow => Self::SlidingWindow(SlidingWindowCounter::new()),
+            RateLimitAlgorithm::TokenBucket => Self::TokenBucket(TokenBucket::new(limit)),
+        }
+    }
+
+    fn algorithm(&self) -> RateLimitAlgorithm {
+        match self {
+            Self::FixedWindow(_) => RateLimitAlgorithm::FixedWindow,
+            Self::SlidingWindow(_) => RateLimitAlgorithm::SlidingWindow,
+            Self::TokenBucket(_) => RateLimitAlgorithm::TokenBucket,
+        }
+    }
+
+    fn allow(&mut self, limit: u32, window: Duration) -> RateLimitDecision {
+        match self {
+            Self::FixedWindow(bucket) => bucket.allow(limit, window),
+            Self::SlidingWindow(counter) => counter.allow(limit, window),
+            Self::TokenBucket(bucket) => bucket.allow(limit, window),
+        }
+    }
+}
+
+struct Entry {
+    state: RateLimitState,
+    /// Once past, the state is indistinguishable from a fresh one.
+    expires_at: Instant,
+}
+
+/// Buckets are spread over independently locked shards, each an LRU capped
+/// at its share of `max_entries`, so a flood of unique keys evicts the
+/// least recently used buckets instead of growing without bound.
 pub struct InMemoryRateLimitStore {
-    buckets: Mutex<HashMap<String, RateLimitBucket>>,
+    shards: Vec<Mutex<LruCache<String, Entry>>>,
+    hasher: RandomState,
+    metrics: Arc<Metrics>,
 }
 
 impl InMemoryRateLimitStore {
-    pub fn new() -> Self {
+    pub fn new(max_entries: usize, shards: usize, metrics: Arc<Metrics>) -> Self {
+        let shards = shards.max(1);
+        let per_shard = max_entries.div_ceil(shards).max(1);
+
         Self {
-            buckets: Mutex::new(HashMap::new()),
+            shards: (0..shards)
+                .map(|_| Mutex::new(LruCache::new(per_shard)))
+                .collect(),
+            hasher: RandomState::new(),
+            metrics,
         }
     }
+
+    /// Periodically drops expired buckets. The task ends once the store is
+    /// dropped.
+    pub fn spawn_sweeper(self: &Arc<Self>, interval: Duration) {
+        let store: Weak<Self> = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+
+                let Some(store) = store.upgrade() else {
+                    break;
+                };
+
+                store.sweep();
+            }
+        });
+    }
+
+    fn sweep(&self) {
+        let now = Instant::now();
+        let mut removed = 0;
+
+        for shard in &self.shards {
+            let mut shard = shard.lock().unwrap();
+
+            let expired: Vec<String> = shard
+                .iter()
+                .filter(|(_, entry)| entry.expires_at <= now)
+                .map(|(key, _)| key.clone())
+                .collect();
+
+            for key in &expired {
+                shard.remove(key);
+            }
+
+            removed += expired.len();
+        }
+
+        if removed > 0 {
+            self.metrics
+                .rate_limit_buckets
+                .fetch_sub(removed as i64, Ordering::Relaxed);
+            self.metrics
+                .rate_limit_expired
+                .fetch_add(removed as u64, Ordering::Relaxed);
+        }
+    }
+
+    fn shard_for(&self, key: &str) -> &Mutex<LruCache<String, Entry>> {
+        let index = self.hasher.hash_one(key) as usize % self.shards.len();
+        &self.shards[index]
+    }
 }
 
 #[async_trait]
@@ -27,13 +140,48 @@ impl RateLimitStore for InMemoryRateLimitStore {
         key: String,
         limit: u32,
         window: Duration,
-    ) -> Result<bool, RateLimitError> {
-        let mut map = self.buckets.lock().unwrap();
+        algorithm: RateLimitAlgorithm,
+    ) -> Result<RateLimitDecision, RateLimitError> {
+        let mut shard = self.shard_for(&key).lock().unwrap();
+        let now = Instant::now();
+
+        if let Some(entry) = shard.get_mut(&key) {
+            // A rule switched to another algorithm starts from a fresh state.
+            if entry.state.algorithm() != algorithm {
+                entry.state = RateLimitState::new(algorithm, limit, window);
+            }
 
-        let bucket = map
-            .entry(key)
-            .or_insert_with(|| RateLimitBucket::new(window));
+            let decision = entry.state.allow(limit, window);
+            entry.expires_at = now + decision.reset_after;
+
+            return Ok(decision);
+        }
+
+        if shard.len() >= shard.capacity() && shard.remove_lru().is_some() {
+            self.metrics.rate_limit_buckets.fetch_sub(1, Ordering::Relaxed);
+            self.metrics.rate_limit_evictions.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let mut state = RateLimitState::new(algorithm, limit, window);
+        let decision = state.allow(limit, window);
+
+        shard.insert(
+            key,
+            Entry {
+                state,
+                expires_at: now + decision.reset_after,
+            },
+        );
+        self.metrics.rate_limit_buckets.fetch_add(1, Ordering::Relaxed);
+
+        Ok(decision)
+    }
+
+    async fn reset(&self, key: String, _algorithm: RateLimitAlgorithm) -> Result<(), RateLimitError> {
+        if self.shard_for(&key).lock().unwrap().remove(&key).is_some() {
+            self.metrics.rate_limit_buckets.fetch_sub(1, Ordering::Relaxed);
+        }
 
-        Ok(bucket.allow(limit, window))
+        Ok(())
     }
 }
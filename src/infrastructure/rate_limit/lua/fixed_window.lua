@@ -5,11 +5,19 @@ local window = tonumber(ARGV[2])
 local current = redis.call("INCR", key)
 
 if current == 1 then
-    redis.call("EXPIRE", key, window)
+    redis.call("PEXPIRE", key, window)
 end
 
+local reset = redis.call("PTTL", key)
+if reset < 0 then
+    redis.call("PEXPIRE", key, window)
+    reset = window
+end
+
+local allowed = 1
 if current > limit then
-    return 0
+    allowed = 0
 end
 
-return 1
+-- { allowed, remaining, reset_ms, retry_ms }
+return { allowed, math.max(limit - current, 0), reset, reset }
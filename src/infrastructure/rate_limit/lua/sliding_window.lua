@@ -0,0 +1,53 @@
+local key = KEYS[1]
+local limit = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+
+local time = redis.call("TIME")
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local current_window = math.floor(now / window)
+
+local state = redis.call("HMGET", key, "window", "current", "previous")
+local stored_window = tonumber(state[1])
+local current = tonumber(state[2]) or 0
+local previous = tonumber(state[3]) or 0
+
+if stored_window == nil then
+    current = 0
+    previous = 0
+elseif current_window == stored_window + 1 then
+    previous = current
+    current = 0
+elseif current_window > stored_window + 1 then
+    previous = 0
+    current = 0
+end
+
+local left_in_window = (current_window + 1) * window - now
+local estimated = previous * left_in_window / window + current
+
+local allowed = 0
+if estimated + 1 <= limit then
+    current = current + 1
+    estimated = estimated + 1
+    allowed = 1
+end
+
+redis.call("HSET", key, "window", current_window, "current", current, "previous", previous)
+redis.call("PEXPIRE", key, window * 2)
+
+local spare = limit - 1 - current
+local retry = left_in_window
+if spare >= 0 and previous > 0 then
+    retry = math.max(left_in_window - spare * window / previous, 0)
+elseif spare < 0 and current > 0 then
+    -- the full current window becomes the previous one at the boundary
+    retry = left_in_window + window * (1 - (limit - 1) / current)
+end
+
+local reset = left_in_window
+if current > 0 then
+    reset = left_in_window + window
+end
+
+-- { allowed, remaining, reset_ms, retry_ms }
+return { allowed, math.max(math.floor(limit - estimated), 0), math.ceil(reset), math.ceil(retry) }
@@ -0,0 +1,28 @@
+local key = KEYS[1]
+local limit = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+
+local time = redis.call("TIME")
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local refill_per_ms = limit / window
+
+local state = redis.call("HMGET", key, "tokens", "updated_at")
+local tokens = tonumber(state[1]) or limit
+local updated_at = tonumber(state[2]) or now
+
+tokens = math.min(limit, tokens + (now - updated_at) * refill_per_ms)
+
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+
+redis.call("HSET", key, "tokens", tostring(tokens), "updated_at", now)
+redis.call("PEXPIRE", key, window)
+
+local reset = (limit - tokens) / refill_per_ms
+local retry = math.max(1 - tokens, 0) / refill_per_ms
+
+-- { allowed, remaining, reset_ms, retry_ms }
+return { allowed, math.floor(tokens), math.ceil(reset), math.ceil(retry) }
@@ -1,2 +1,10 @@
+pub mod circuit_breaker;
+#[cfg(test)]
+mod conformance_tests;
+pub mod failover_store;
+pub mod guarded_ban_store;
+pub mod in_memory_ban_store;
 pub mod in_memory_store;
+pub mod redis_backends;
+pub mod redis_ban_store;
 pub mod redis_store;
@@ -0,0 +1,150 @@
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::sentinel::SentinelClient;
+use redis::{Cmd, ErrorKind, Pipeline, RedisError, RedisFuture, RedisResult, ServerErrorKind, Value};
+use std::sync::{Arc, RwLock};
+use tokio::sync::Mutex;
+
+use crate::domain::rate_limit::ban_store::BanStore;
+use crate::domain::rate_limit::store::RateLimitStore;
+use crate::infrastructure::rate_limit::redis_ban_store::RedisBanStore;
+use crate::infrastructure::rate_limit::redis_store::RedisRateLimitStore;
+use crate::shared::config::RedisTopology;
+
+/// Redis-backed stores sharing one connection.
+pub struct RedisBackends {
+    pub rate_limit: Arc<dyn RateLimitStore>,
+    pub bans: Arc<dyn BanStore>,
+}
+
+impl RedisBackends {
+    async fn new<C>(conn: C) -> RedisResult<Self>
+    where
+        C: ConnectionLike + Clone + Send + Sync + 'static,
+    {
+        Ok(Self {
+            rate_limit: Arc::new(RedisRateLimitStore::new(conn.clone()).await?),
+            bans: Arc::new(RedisBanStore::new(conn)),
+        })
+    }
+}
+
+/// Connects to Redis according to `topology`.
+pub async fn connect(topology: &RedisTopology) -> RedisResult<RedisBackends> {
+    match topology {
+        RedisTopology::Standalone { url } => {
+            let conn = redis::Client::open(url.as_str())?
+                .get_multiplexed_async_connection()
+                .await?;
+
+            RedisBackends::new(conn).await
+        }
+        RedisTopology::Cluster { nodes } => {
+            let conn = redis::cluster::ClusterClient::new(nodes.clone())?
+                .get_async_connection()
+                .await?;
+
+            RedisBackends::new(conn).await
+        }
+        RedisTopology::Sentinel { nodes, master_name } => {
+            let client = SentinelClient::build(
+                nodes.clone(),
+                master_name.clone(),
+                None,
+                redis::sentinel::SentinelServerType::Master,
+            )?;
+
+            let conn = SentinelConnection::connect(client).await?;
+
+            RedisBackends::new(conn).await
+        }
+    }
+}
+
+/// Multiplexed connection to whichever node Sentinel currently reports as
+/// master. A failover leaves the old connection pointing at a replica that
+/// answers writes with `READONLY`, or at a node that is gone; either error
+/// makes the master be looked up again, and later commands use the new one.
+/// The failing command itself is not retried, since it may have run.
+#[derive(Clone)]
+struct SentinelConnection {
+    client: Arc<Mutex<SentinelClient>>,
+    /// The connection and a generation bumped on every reconnect, so that
+    /// concurrent failures on the same connection reconnect only once.
+    current: Arc<RwLock<(u64, MultiplexedConnection)>>,
+}
+
+impl SentinelConnection {
+    async fn connect(mut client: SentinelClient) -> RedisResult<Self> {
+        let conn = client.get_async_connection().await?;
+
+        Ok(Self {
+            client: Arc::new(Mutex::new(client)),
+            current: Arc::new(RwLock::new((0, conn))),
+        })
+    }
+
+    fn current(&self) -> (u64, MultiplexedConnection) {
+        self.current.read().unwrap().clone()
+    }
+
+    async fn after_error(&self, generation: u64, err: &RedisError) {
+        let demoted = err.kind() == ErrorKind::Server(ServerErrorKind::ReadOnly);
+
+        if !demoted && !err.is_unrecoverable_error() {
+            return;
+        }
+
+        let mut client = self.client.lock().await;
+
+        if self.current.read().unwrap().0 != generation {
+            return;
+        }
+
+        match client.get_async_connection().await {
+            Ok(conn) => {
+                tracing::warn!(error = %err, "redis master connection lost, reconnected through sentinel");
+                *self.current.write().unwrap() = (generation + 1, conn);
+            }
+            Err(reconnect_err) => {
+                tracing::warn!(error = %reconnect_err, "failed to resolve redis master through sentinel");
+            }
+        }
+    }
+}
+
+impl ConnectionLike for SentinelConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            let (generation, mut conn) = self.current();
+            let result = conn.req_packed_command(cmd).await;
+
+            if let Err(err) = &result {
+                self.after_error(generation, err).await;
+            }
+
+            result
+        })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            let (generation, mut conn) = self.current();
+            let result = conn.req_packed_commands(cmd, offset, count).await;
+
+            if let Err(err) = &result {
+                self.after_error(generation, err).await;
+            }
+
+            result
+        })
+    }
+
+    fn get_db(&self) -> i64 {
+        self.current.read().unwrap().1.get_db()
+    }
+}
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use redis::aio::ConnectionLike;
+use std::net::IpAddr;
+
+use crate::domain::rate_limit::ban::IpBan;
+use crate::domain::rate_limit::ban_store::{BanStore, BanStoreError};
+
+/// Keys share the `{bans}` hash tag so they live in one cluster slot and can
+/// be read together.
+const INDEX_KEY: &str = "{bans}:index";
+
+fn ban_key(ip: IpAddr) -> String {
+    format!("{{bans}}:ip:{ip}")
+}
+
+/// Each ban is a JSON value expiring with the ban. A sorted set scored by
+/// expiry indexes them for listing.
+pub struct RedisBanStore<C> {
+    conn: C,
+}
+
+impl<C> RedisBanStore<C> {
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+}
+
+#[async_trait]
+impl<C> BanStore for RedisBanStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    async fn ban(&self, ban: IpBan) -> Result<(), BanStoreError> {
+        let ttl_ms = (ban.expires_at - Utc::now()).num_milliseconds();
+        if ttl_ms <= 0 {
+            return Ok(());
+        }
+
+        let value = serde_json::to_string(&ban).map_err(|_| BanStoreError::StorageError)?;
+        let mut conn = self.conn.clone();
+
+        redis::pipe()
+            .atomic()
+            .cmd("SET")
+            .arg(ban_key(ban.ip))
+            .arg(value)
+            .arg("PX")
+            .arg(ttl_ms)
+            .ignore()
+            .cmd("ZADD")
+            .arg(INDEX_KEY)
+            .arg(ban.expires_at.timestamp_millis())
+            .arg(ban.ip.to_string())
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|_| BanStoreError::StorageError)
+    }
+
+    async fn find(&self, ip: IpAddr) -> Result<Option<IpBan>, BanStoreError> {
+        let mut conn = self.conn.clone();
+
+        let value: Option<String> = redis::cmd("GET")
+            .arg(ban_key(ip))
+            .query_async(&mut conn)
+            .await
+            .map_err(|_| BanStoreError::StorageError)?;
+
+        Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    async fn list(&self) -> Result<Vec<IpBan>, BanStoreError> {
+        let mut conn = self.conn.clone();
+
+        let (ips,): (Vec<String>,) = redis::pipe()
+            .atomic()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(INDEX_KEY)
+            .arg("-inf")
+            .arg(Utc::now().timestamp_millis())
+            .ignore()
+            .cmd("ZRANGE")
+            .arg(INDEX_KEY)
+            .arg(0)
+            .arg(-1)
+            .query_async(&mut conn)
+            .await
+            .map_err(|_| BanStoreError::StorageError)?;
+
+        if ips.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = ips
+            .iter()
+            .filter_map(|ip| ip.parse().ok())
+            .map(ban_key)
+            .collect();
+
+        let values: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(keys)
+            .query_async(&mut conn)
+            .await
+            .map_err(|_| BanStoreError::StorageError)?;
+
+        Ok(values
+            .into_iter()
+            .flatten()
+            .filter_map(|v| serde_json::from_str(&v).ok())
+            .collect())
+    }
+
+    async fn lift(&self, ip: IpAddr) -> Result<bool, BanStoreError> {
+        let mut conn = self.conn.clone();
+
+        let (deleted,): (i64,) = redis::pipe()
+            .atomic()
+            .cmd("DEL")
+            .arg(ban_key(ip))
+            .cmd("ZREM")
+            .arg(INDEX_KEY)
+            .arg(ip.to_string())
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(|_| BanStoreError::StorageError)?;
+
+        Ok(deleted > 0)
+    }
+}
@@ -1,45 +1,115 @@
 use async_trait::async_trait;
-use redis::aio::MultiplexedConnection;
-use redis::Script;
-use std::sync::Arc;
+use redis::aio::ConnectionLike;
+use redis::{RedisResult, Script};
 use std::time::Duration;
-use tokio::sync::Mutex;
 
+use crate::domain::rate_limit::algorithm::RateLimitAlgorithm;
+use crate::domain::rate_limit::decision::RateLimitDecision;
 use crate::domain::rate_limit::store::{RateLimitError, RateLimitStore};
 
-pub struct RedisRateLimitStore {
-    conn: Arc<Mutex<MultiplexedConnection>>,
+/// All scripts take `KEYS[1] = key`, `ARGV[1] = limit`, `ARGV[2] = window (ms)`
+/// and return `{ allowed, remaining, reset_ms, retry_ms }`.
+struct RateLimitScripts {
+    fixed_window: Script,
+    sliding_window: Script,
+    token_bucket: Script,
 }
 
-impl RedisRateLimitStore {
-    pub async fn new(client: redis::Client) -> Result<Self, redis::RedisError> {
-        let conn = client.get_multiplexed_async_connection().await?;
-        Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
-        })
+impl RateLimitScripts {
+    fn new() -> Self {
+        Self {
+            fixed_window: Script::new(include_str!("lua/fixed_window.lua")),
+            sliding_window: Script::new(include_str!("lua/sliding_window.lua")),
+            token_bucket: Script::new(include_str!("lua/token_bucket.lua")),
+        }
+    }
+
+    fn for_algorithm(&self, algorithm: RateLimitAlgorithm) -> &Script {
+        match algorithm {
+            RateLimitAlgorithm::FixedWindow => &self.fixed_window,
+            RateLimitAlgorithm::SlidingWindow => &self.sliding_window,
+            RateLimitAlgorithm::TokenBucket => &self.token_bucket,
+        }
+    }
+
+    fn all(&self) -> [&Script; 3] {
+        [&self.fixed_window, &self.sliding_window, &self.token_bucket]
+    }
+}
+
+/// Runs the rate limit scripts over a multiplexed (or cluster) connection.
+/// The connection is cloned per call instead of locked, so concurrent checks
+/// are pipelined over the same socket.
+pub struct RedisRateLimitStore<C> {
+    conn: C,
+    scripts: RateLimitScripts,
+}
+
+impl<C> RedisRateLimitStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    /// Loads the scripts up front so the first checks hit `EVALSHA` directly.
+    /// Scripts flushed later (restart, failover, `SCRIPT FLUSH`) are reloaded
+    /// on `NOSCRIPT` by `Script::invoke_async`.
+    pub async fn new(conn: C) -> RedisResult<Self> {
+        let scripts = RateLimitScripts::new();
+
+        let mut load_conn = conn.clone();
+        for script in scripts.all() {
+            script.load_async(&mut load_conn).await?;
+        }
+
+        Ok(Self { conn, scripts })
     }
 }
 
 #[async_trait]
-impl RateLimitStore for RedisRateLimitStore {
+impl<C> RateLimitStore for RedisRateLimitStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
     async fn check(
         &self,
         key: String,
         limit: u32,
         window: Duration,
-    ) -> Result<bool, RateLimitError> {
-        let script = Script::new(include_str!("lua/fixed_window.lua"));
-
-        let mut conn = self.conn.lock().await;
+        algorithm: RateLimitAlgorithm,
+    ) -> Result<RateLimitDecision, RateLimitError> {
+        let mut conn = self.conn.clone();
 
-        let allowed: i32 = script
-            .key(key)
+        let (allowed, remaining, reset_ms, retry_ms): (i64, i64, i64, i64) = self
+            .scripts
+            .for_algorithm(algorithm)
+            .key(algorithm_key(&key, algorithm))
             .arg(limit)
-            .arg(window.as_secs() as i64)
-            .invoke_async(&mut *conn)
+            .arg(window.as_millis() as i64)
+            .invoke_async(&mut conn)
             .await
             .map_err(|_| RateLimitError::StorageError)?;
 
-        Ok(allowed == 1)
+        Ok(RateLimitDecision::new(
+            allowed == 1,
+            limit,
+            remaining.max(0) as u32,
+            Duration::from_millis(reset_ms.max(0) as u64),
+            Duration::from_millis(retry_ms.max(0) as u64),
+        ))
     }
+
+    async fn reset(&self, key: String, algorithm: RateLimitAlgorithm) -> Result<(), RateLimitError> {
+        let mut conn = self.conn.clone();
+
+        redis::cmd("DEL")
+            .arg(algorithm_key(&key, algorithm))
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|_| RateLimitError::StorageError)
+    }
+}
+
+/// Each algorithm keeps a differently shaped value, so they must not share
+/// a key.
+fn algorithm_key(key: &str, algorithm: RateLimitAlgorithm) -> String {
+    format!("{key}:{}", algorithm.as_str())
 }
@@ -1,41 +1,293 @@
 use argon2::{
     password_hash::{phc::PasswordHash, PasswordHasher as _, PasswordVerifier},
-    Argon2,
+    Algorithm, Argon2, KeyId, Params, ParamsBuilder, Version,
 };
+use async_trait::async_trait;
+use std::collections::HashMap;
+use thiserror::Error;
 
 use crate::application::security::password_hasher::{PasswordHashError, PasswordHasher};
+use crate::infrastructure::security::blocking_pool::BlockingPool;
+
+#[derive(Debug, Clone)]
+pub struct Argon2Config {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    /// Secret mixed into every hash. Hashes record `pepper_id` as their
+    /// Argon2 key id so the pepper can be introduced on a live system.
+    pub pepper: Option<String>,
+    pub pepper_id: String,
+    /// Retired peppers by id. Hashes made with them still verify and are
+    /// rehashed with the current pepper on the next login.
+    pub previous_peppers: HashMap<String, String>,
+}
+
+impl Default for Argon2Config {
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+            pepper: None,
+            pepper_id: "1".to_string(),
+            previous_peppers: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("invalid Argon2 configuration: {0}")]
+pub struct InvalidArgon2Config(String);
 
 pub struct Argon2PasswordHasher {
+    /// Hashes new passwords, with the pepper when one is configured.
     argon2: Argon2<'static>,
+    /// Verifies hashes made before the pepper was configured.
+    unpeppered: Argon2<'static>,
+    /// Verifies hashes made with a retired pepper, keyed by its id.
+    previous: HashMap<Vec<u8>, Argon2<'static>>,
+    params: Params,
+    pepper_id: Option<Vec<u8>>,
+    pool: BlockingPool,
 }
 
 impl Argon2PasswordHasher {
-    pub fn new() -> Self {
-        Self {
-            argon2: Argon2::default(),
+    pub fn new(config: &Argon2Config, pool: BlockingPool) -> Result<Self, InvalidArgon2Config> {
+        let mut builder = ParamsBuilder::new();
+        builder
+            .m_cost(config.memory_kib)
+            .t_cost(config.iterations)
+            .p_cost(config.parallelism);
+
+        if config.pepper.is_some() {
+            let keyid = KeyId::new(config.pepper_id.as_bytes())
+                .map_err(|e| InvalidArgon2Config(format!("pepper id: {e}")))?;
+            builder.keyid(keyid);
         }
+
+        let params = builder
+            .build()
+            .map_err(|e| InvalidArgon2Config(e.to_string()))?;
+
+        let unpeppered = Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone());
+
+        // Secrets live for the rest of the process, like the hasher itself.
+        let peppered = |pepper: &str| {
+            let secret: &'static [u8] = Box::leak(pepper.as_bytes().to_vec().into_boxed_slice());
+
+            Argon2::new_with_secret(secret, Algorithm::Argon2id, Version::V0x13, params.clone())
+                .map_err(|e| InvalidArgon2Config(format!("pepper: {e}")))
+        };
+
+        let argon2 = match &config.pepper {
+            Some(pepper) => peppered(pepper)?,
+            None => unpeppered.clone(),
+        };
+
+        let mut previous = HashMap::new();
+
+        for (id, pepper) in &config.previous_peppers {
+            if config.pepper.is_some() && *id == config.pepper_id {
+                return Err(InvalidArgon2Config(format!(
+                    "previous pepper `{id}` reuses the current pepper id"
+                )));
+            }
+
+            previous.insert(id.clone().into_bytes(), peppered(pepper)?);
+        }
+
+        Ok(Self {
+            argon2,
+            unpeppered,
+            previous,
+            params,
+            pepper_id: config.pepper.as_ref().map(|_| config.pepper_id.clone().into_bytes()),
+            pool,
+        })
     }
 }
 
+#[async_trait]
 impl PasswordHasher for Argon2PasswordHasher {
-    fn hash(&self, plain: &str) -> Result<String, PasswordHashError> {
-
-        let hash = self
-            .argon2
-            .hash_password(plain.as_bytes())
-            .map_err(|_| PasswordHashError::HashFailed)?
-            .to_string();
+    async fn hash(&self, plain: &str) -> Result<String, PasswordHashError> {
+        let argon2 = self.argon2.clone();
+        let plain = plain.to_owned();
 
-        Ok(hash)
+        self.pool
+            .run(move || {
+                argon2
+                    .hash_password(plain.as_bytes())
+                    .map(|hash| hash.to_string())
+            })
+            .await
+            .and_then(Result::ok)
+            .ok_or(PasswordHashError::HashFailed)
     }
 
-    fn verify(&self, plain: &str, hash: &str) -> Result<bool, PasswordHashError> {
+    async fn verify(&self, plain: &str, hash: &str) -> Result<bool, PasswordHashError> {
         let parsed_hash =
             PasswordHash::new(hash).map_err(|_| PasswordHashError::VerifyFailed)?;
 
-        Ok(self
-            .argon2
-            .verify_password(plain.as_bytes(), &parsed_hash)
-            .is_ok())
+        let keyid = Params::try_from(&parsed_hash)
+            .map(|p| p.keyid().to_vec())
+            .map_err(|_| PasswordHashError::VerifyFailed)?;
+
+        let argon2 = if keyid.is_empty() {
+            self.unpeppered.clone()
+        } else if self.pepper_id.as_deref() == Some(keyid.as_slice()) {
+            self.argon2.clone()
+        } else if let Some(previous) = self.previous.get(&keyid) {
+            previous.clone()
+        } else {
+            // Made with a pepper that is no longer configured.
+            return Ok(false);
+        };
+
+        let plain = plain.to_owned();
+
+        self.pool
+            .run(move || argon2.verify_password(plain.as_bytes(), &parsed_hash).is_ok())
+            .await
+            .ok_or(PasswordHashError::VerifyFailed)
+    }
+
+    fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(hash) else {
+            return true;
+        };
+
+        let Ok(params) = Params::try_from(&parsed) else {
+            return true;
+        };
+
+        parsed.algorithm.as_str() != Algorithm::Argon2id.ident().as_str()
+            || parsed.version != Some(Version::V0x13.into())
+            || params.m_cost() != self.params.m_cost()
+            || params.t_cost() != self.params.t_cost()
+            || params.p_cost() != self.params.p_cost()
+            || params.keyid() != self.params.keyid()
+    }
+
+    fn is_supported(&self, hash: &str) -> bool {
+        PasswordHash::new(hash).is_ok_and(|parsed| parsed.algorithm.as_str().starts_with("argon2"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn fast_config() -> Argon2Config {
+        Argon2Config {
+            memory_kib: 1024,
+            iterations: 1,
+            parallelism: 1,
+            ..Argon2Config::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn rotated_pepper_still_verifies_and_is_rehashed() {
+        let old = Argon2PasswordHasher::new(
+            &Argon2Config {
+                pepper: Some("old secret".into()),
+                pepper_id: "1".into(),
+                ..fast_config()
+            },
+            BlockingPool::new(1),
+        )
+        .unwrap();
+        let hash = old.hash("correct horse").await.unwrap();
+
+        let rotated = Argon2PasswordHasher::new(
+            &Argon2Config {
+                pepper: Some("new secret".into()),
+                pepper_id: "2".into(),
+                previous_peppers: HashMap::from([("1".into(), "old secret".into())]),
+                ..fast_config()
+            },
+            BlockingPool::new(1),
+        )
+        .unwrap();
+
+        assert!(rotated.verify("correct horse", &hash).await.unwrap());
+        assert!(!rotated.verify("wrong horse", &hash).await.unwrap());
+        assert!(rotated.needs_rehash(&hash));
+
+        let upgraded = rotated.hash("correct horse").await.unwrap();
+        assert!(rotated.verify("correct horse", &upgraded).await.unwrap());
+        assert!(!rotated.needs_rehash(&upgraded));
+    }
+
+    #[tokio::test]
+    async fn unknown_pepper_id_is_rejected() {
+        let old = Argon2PasswordHasher::new(
+            &Argon2Config {
+                pepper: Some("old secret".into()),
+                ..fast_config()
+            },
+            BlockingPool::new(1),
+        )
+        .unwrap();
+        let hash = old.hash("correct horse").await.unwrap();
+
+        let rotated = Argon2PasswordHasher::new(
+            &Argon2Config {
+                pepper: Some("new secret".into()),
+                pepper_id: "2".into(),
+                ..fast_config()
+            },
+            BlockingPool::new(1),
+        )
+        .unwrap();
+
+        assert!(!rotated.verify("correct horse", &hash).await.unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn concurrent_logins_do_not_stall_the_runtime() {
+        let config = Argon2Config {
+            memory_kib: 8 * 1024,
+            iterations: 2,
+            parallelism: 1,
+            ..Argon2Config::default()
+        };
+        let hasher = Arc::new(Argon2PasswordHasher::new(&config, BlockingPool::new(2)).unwrap());
+        let hash = hasher.hash("correct horse").await.unwrap();
+
+        let started = Instant::now();
+        assert!(hasher.verify("correct horse", &hash).await.unwrap());
+        let one_verify = started.elapsed();
+
+        // Far more logins than worker threads: hashing on the workers would
+        // hold up everything else for at least one verify.
+        let logins: Vec<_> = (0..16)
+            .map(|_| {
+                let hasher = hasher.clone();
+                let hash = hash.clone();
+                tokio::spawn(async move { hasher.verify("correct horse", &hash).await })
+            })
+            .collect();
+
+        let tick = Duration::from_millis(5);
+        let mut worst_delay = Duration::ZERO;
+
+        for _ in 0..20 {
+            let started = Instant::now();
+            tokio::time::sleep(tick).await;
+            worst_delay = worst_delay.max(started.elapsed().saturating_sub(tick));
+        }
+
+        for login in logins {
+            assert!(login.await.unwrap().unwrap());
+        }
+
+        assert!(
+            worst_delay < one_verify,
+            "runtime stalled for {worst_delay:?} (one verify takes {one_verify:?})"
+        );
     }
 }
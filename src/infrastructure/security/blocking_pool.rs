@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Runs CPU-heavy closures on Tokio's blocking threads, at most
+/// `max_concurrency` at a time. Callers beyond that wait asynchronously
+/// instead of piling up blocking threads.
+#[derive(Clone)]
+pub struct BlockingPool {
+    permits: Arc<Semaphore>,
+}
+
+impl BlockingPool {
+    pub fn new(max_concurrency: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrency.max(1))),
+        }
+    }
+
+    /// Returns `None` if the closure panicked.
+    ///
+    /// The permit moves into the blocking job, which keeps running if the
+    /// caller is dropped (a client disconnecting mid-login); the slot is only
+    /// freed once the work is actually done.
+    pub async fn run<F, T>(&self, f: F) -> Option<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = self.permits.clone().acquire_owned().await.ok()?;
+
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            f()
+        })
+        .await
+        .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Blocks for `duration` while tracking how many jobs overlap.
+    fn job(
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+        duration: Duration,
+    ) -> impl FnOnce() + Send + 'static {
+        move || {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(duration);
+            running.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelled_callers_keep_their_slot_until_the_job_ends() {
+        let pool = BlockingPool::new(1);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let cancelled = tokio::spawn({
+            let pool = pool.clone();
+            let job = job(running.clone(), peak.clone(), Duration::from_millis(200));
+            async move { pool.run(job).await }
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        cancelled.abort();
+
+        pool.run(job(running, peak.clone(), Duration::from_millis(10)))
+            .await
+            .unwrap();
+
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+}
@@ -3,12 +3,26 @@ use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::application::security::jwt::{JwtClaims, JwtError, JwtService};
+use crate::application::security::jwt::{JwtClaims, JwtError, JwtService, TokenScope};
+use crate::domain::user::value_objects::UserRole;
+
+fn default_role() -> String {
+    UserRole::User.as_str().to_string()
+}
+
+fn default_scope() -> String {
+    TokenScope::Full.as_str().to_string()
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
     sub: String,
     exp: usize,
+    // Tokens issued before roles were embedded carry none.
+    #[serde(default = "default_role")]
+    role: String,
+    #[serde(default = "default_scope")]
+    scope: String,
 }
 
 pub struct JwtServiceImpl {
@@ -28,12 +42,19 @@ impl JwtServiceImpl {
 }
 
 impl JwtService for JwtServiceImpl {
-    fn generate(&self, user_id: Uuid) -> Result<String, JwtError> {
+    fn generate(
+        &self,
+        user_id: Uuid,
+        role: UserRole,
+        scope: TokenScope,
+    ) -> Result<String, JwtError> {
         let exp = (Utc::now() + Duration::seconds(self.ttl_seconds)).timestamp();
 
         let claims = Claims {
             sub: user_id.to_string(),
             exp: exp as usize,
+            role: role.as_str().to_string(),
+            scope: scope.as_str().to_string(),
         };
 
         encode(&Header::default(), &claims, &self.encoding)
@@ -51,9 +72,15 @@ impl JwtService for JwtServiceImpl {
         let user_id = Uuid::parse_str(&data.claims.sub)
             .map_err(|_| JwtError::InvalidToken)?;
 
+        let role = UserRole::parse(&data.claims.role)
+            .map_err(|_| JwtError::InvalidToken)?;
+
+        let scope = TokenScope::parse(&data.claims.scope).ok_or(JwtError::InvalidToken)?;
+
         Ok(JwtClaims {
             sub: user_id,
-            exp: data.claims.exp as i64,
+            role,
+            scope,
         })
     }
 }
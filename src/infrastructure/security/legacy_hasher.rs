@@ -0,0 +1,203 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use pbkdf2::password_hash::{PasswordHash, PasswordVerifier};
+use subtle::ConstantTimeEq;
+
+use crate::application::security::password_hasher::{PasswordHashError, PasswordHasher};
+use crate::infrastructure::security::argon2_hasher::Argon2PasswordHasher;
+use crate::infrastructure::security::blocking_pool::BlockingPool;
+
+// Guard against imported hashes that would take minutes, or gigabytes, to
+// verify. Each sits well above what the source systems ever defaulted to.
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+const MAX_BCRYPT_COST: u32 = 16;
+const MAX_SCRYPT_MEMORY_BYTES: u64 = 256 * 1024 * 1024;
+const MAX_SCRYPT_PARALLELISM: u32 = 16;
+
+/// Hash formats carried over from systems users were migrated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LegacyFormat {
+    /// `$2a$`, `$2b$`, `$2y$`.
+    Bcrypt,
+    /// PHC `$pbkdf2-sha256$`.
+    Pbkdf2Phc,
+    /// Django's `pbkdf2_sha256$<iterations>$<salt>$<base64 hash>`.
+    Pbkdf2Django,
+    /// PHC `$scrypt$`.
+    Scrypt,
+}
+
+impl LegacyFormat {
+    fn detect(hash: &str) -> Option<Self> {
+        if ["$2a$", "$2b$", "$2y$"].iter().any(|p| hash.starts_with(p)) {
+            Some(Self::Bcrypt)
+        } else if hash.starts_with("$pbkdf2-sha256$") {
+            Some(Self::Pbkdf2Phc)
+        } else if hash.starts_with("pbkdf2_sha256$") {
+            Some(Self::Pbkdf2Django)
+        } else if hash.starts_with("$scrypt$") {
+            Some(Self::Scrypt)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the hash's cost parameters are cheap enough to verify.
+    fn within_limits(self, hash: &str) -> bool {
+        match self {
+            Self::Bcrypt => hash
+                .parse::<bcrypt::HashParts>()
+                .is_ok_and(|parts| parts.get_cost() <= MAX_BCRYPT_COST),
+            Self::Pbkdf2Phc => PasswordHash::new(hash)
+                .ok()
+                .and_then(|parsed| pbkdf2::Params::try_from(&parsed).ok())
+                .is_some_and(|params| params.rounds > 0 && params.rounds <= MAX_PBKDF2_ITERATIONS),
+            Self::Pbkdf2Django => hash
+                .split('$')
+                .nth(1)
+                .and_then(|iterations| iterations.parse::<u32>().ok())
+                .is_some_and(|iterations| iterations > 0 && iterations <= MAX_PBKDF2_ITERATIONS),
+            Self::Scrypt => PasswordHash::new(hash)
+                .ok()
+                .and_then(|parsed| scrypt::Params::try_from(&parsed).ok())
+                .is_some_and(|params| {
+                    let memory = (128 * u128::from(params.r())) << params.log_n();
+
+                    memory <= u128::from(MAX_SCRYPT_MEMORY_BYTES) && params.p() <= MAX_SCRYPT_PARALLELISM
+                }),
+        }
+    }
+
+    fn verify(self, plain: &str, hash: &str) -> bool {
+        match self {
+            Self::Bcrypt => bcrypt::verify(plain, hash).unwrap_or(false),
+            Self::Pbkdf2Phc => PasswordHash::new(hash)
+                .is_ok_and(|parsed| pbkdf2::Pbkdf2.verify_password(plain.as_bytes(), &parsed).is_ok()),
+            Self::Pbkdf2Django => verify_django_pbkdf2(plain, hash),
+            Self::Scrypt => PasswordHash::new(hash)
+                .is_ok_and(|parsed| scrypt::Scrypt.verify_password(plain.as_bytes(), &parsed).is_ok()),
+        }
+    }
+}
+
+fn verify_django_pbkdf2(plain: &str, hash: &str) -> bool {
+    let mut parts = hash.splitn(4, '$');
+    let (Some(_), Some(iterations), Some(salt), Some(expected)) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+
+    let Ok(iterations) = iterations.parse::<u32>() else {
+        return false;
+    };
+
+    let Ok(expected) = general_purpose::STANDARD.decode(expected) else {
+        return false;
+    };
+    if expected.is_empty() {
+        return false;
+    }
+
+    let mut derived = vec![0u8; expected.len()];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(plain.as_bytes(), salt.as_bytes(), iterations, &mut derived);
+
+    derived.ct_eq(&expected).into()
+}
+
+/// Verifies Argon2 and legacy hashes, and always hashes with Argon2. Legacy
+/// hashes report `needs_rehash`, so they are upgraded on the next login.
+pub struct MultiAlgorithmPasswordHasher {
+    argon2: Argon2PasswordHasher,
+    pool: BlockingPool,
+}
+
+impl MultiAlgorithmPasswordHasher {
+    pub fn new(argon2: Argon2PasswordHasher, pool: BlockingPool) -> Self {
+        Self { argon2, pool }
+    }
+}
+
+#[async_trait]
+impl PasswordHasher for MultiAlgorithmPasswordHasher {
+    async fn hash(&self, plain: &str) -> Result<String, PasswordHashError> {
+        self.argon2.hash(plain).await
+    }
+
+    async fn verify(&self, plain: &str, hash: &str) -> Result<bool, PasswordHashError> {
+        let Some(format) = LegacyFormat::detect(hash) else {
+            return self.argon2.verify(plain, hash).await;
+        };
+
+        if !format.within_limits(hash) {
+            return Ok(false);
+        }
+
+        let plain = plain.to_owned();
+        let hash = hash.to_owned();
+
+        self.pool
+            .run(move || format.verify(&plain, &hash))
+            .await
+            .ok_or(PasswordHashError::VerifyFailed)
+    }
+
+    fn needs_rehash(&self, hash: &str) -> bool {
+        LegacyFormat::detect(hash).is_some() || self.argon2.needs_rehash(hash)
+    }
+
+    fn is_supported(&self, hash: &str) -> bool {
+        match LegacyFormat::detect(hash) {
+            Some(format) => format.within_limits(hash),
+            None => self.argon2.is_supported(hash),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::security::argon2_hasher::Argon2Config;
+
+    const SALT: &str = "c2FsdHNhbHQ";
+    const OUTPUT: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+    fn hasher() -> MultiAlgorithmPasswordHasher {
+        let pool = BlockingPool::new(1);
+        let argon2 = Argon2PasswordHasher::new(&Argon2Config::default(), pool.clone()).unwrap();
+
+        MultiAlgorithmPasswordHasher::new(argon2, pool)
+    }
+
+    #[tokio::test]
+    async fn oversized_costs_are_refused_at_import_and_login() {
+        let hasher = hasher();
+        let bcrypt = bcrypt::hash("correct horse", 4).unwrap().replacen("$04$", "$31$", 1);
+
+        let oversized = [
+            bcrypt,
+            format!("$pbkdf2-sha256$i=100000000,l=32${SALT}${OUTPUT}"),
+            format!("pbkdf2_sha256$100000000${SALT}${OUTPUT}="),
+            format!("$scrypt$ln=24,r=8,p=1${SALT}${OUTPUT}"),
+            format!("$scrypt$ln=10,r=8,p=64${SALT}${OUTPUT}"),
+        ];
+
+        for hash in oversized {
+            assert!(!hasher.is_supported(&hash), "{hash} was accepted for import");
+            assert!(!hasher.verify("correct horse", &hash).await.unwrap(), "{hash} was verified");
+        }
+    }
+
+    #[tokio::test]
+    async fn hashes_within_limits_still_verify() {
+        let hasher = hasher();
+        let bcrypt = bcrypt::hash("correct horse", 4).unwrap();
+
+        assert!(hasher.is_supported(&bcrypt));
+        assert!(hasher.verify("correct horse", &bcrypt).await.unwrap());
+
+        assert!(hasher.is_supported(&format!("$pbkdf2-sha256$i=600000,l=32${SALT}${OUTPUT}")));
+        assert!(hasher.is_supported(&format!("pbkdf2_sha256$600000${SALT}${OUTPUT}=")));
+        assert!(hasher.is_supported(&format!("$scrypt$ln=17,r=8,p=1${SALT}${OUTPUT}")));
+    }
+}
@@ -1,2 +1,5 @@
 pub mod argon2_hasher;
-pub mod jwt_service;
\ No newline at end of file
+pub mod blocking_pool;
+pub mod hibp_filter;
+pub mod jwt_service;
+pub mod legacy_hasher;
@@ -4,6 +4,7 @@ mod http;
 mod infrastructure;
 mod shared;
 
+use infrastructure::persistence::postgres_password_history_repository::PostgresPasswordHistoryRepository;
 use infrastructure::persistence::postgres_refresh_token_repository::PostgresRefreshTokenRepository;
 use shared::{config::AppConfig, state::AppState};
 use sqlx::postgres::PgPoolOptions;
@@ -12,13 +13,29 @@ use std::panic;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 use crate::application::audit::audit_logger::AuditLogger;
+use crate::application::auth::login_throttle::LoginThrottle;
+use crate::application::rate_limit::auto_ban::AutoBan;
+use crate::application::security::password_policy::PasswordPolicy;
+use crate::domain::rate_limit::ban_store::BanStore;
 use crate::domain::rate_limit::store::RateLimitStore;
+use crate::http::client_ip::ClientIpResolver;
+use crate::http::concurrency::ConcurrencyLimiter;
+use crate::http::ip_access::IpAccessList;
+use crate::shared::metrics::Metrics;
+use crate::http::middleware::rate_limit_policy::RateLimitPolicy;
 use crate::infrastructure::persistence::postgres_audit_log_repository::PostgresAuditLogRepository;
+use crate::infrastructure::rate_limit::circuit_breaker::CircuitBreaker;
+use crate::infrastructure::rate_limit::failover_store::FailoverRateLimitStore;
+use crate::infrastructure::rate_limit::guarded_ban_store::GuardedBanStore;
 use crate::infrastructure::rate_limit::in_memory_store::InMemoryRateLimitStore;
-use crate::infrastructure::rate_limit::redis_store::RedisRateLimitStore;
+use crate::infrastructure::rate_limit::in_memory_ban_store::InMemoryBanStore;
+use crate::infrastructure::rate_limit::redis_backends;
 use infrastructure::{
     persistence::postgres_user_repository::PostgresUserRepository,
-    security::{argon2_hasher::Argon2PasswordHasher, jwt_service::JwtServiceImpl},
+    security::{
+        argon2_hasher::Argon2PasswordHasher, blocking_pool::BlockingPool,
+        hibp_filter::HibpBloomFilter, jwt_service::JwtServiceImpl, legacy_hasher::MultiAlgorithmPasswordHasher,
+    },
 };
 use std::sync::Arc;
 use tracing::error;
@@ -26,6 +43,19 @@ use tracing_appender::rolling::{RollingFileAppender, Rotation};
 
 #[tokio::main]
 async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("build-hibp-filter") => {
+            build_hibp_filter(&args[1..]);
+            return;
+        }
+        Some("openapi") => {
+            println!("{}", http::routes::openapi().to_pretty_json().expect("failed to render OpenAPI document"));
+            return;
+        }
+        _ => {}
+    }
+
     dotenvy::dotenv().ok();
 
     init_logging();
@@ -40,37 +70,130 @@ async fn main() {
         .expect("failed to connect to database");
 
     let user_repo = Arc::new(PostgresUserRepository::new(db.clone()));
-    let password_hasher = Arc::new(Argon2PasswordHasher::new());
+    let hashing_pool = BlockingPool::new(config.password_hash_concurrency);
+    let argon2_hasher = Argon2PasswordHasher::new(&config.argon2, hashing_pool.clone())
+        .unwrap_or_else(|e| panic!("{e}"));
+    let password_hasher = Arc::new(MultiAlgorithmPasswordHasher::new(argon2_hasher, hashing_pool));
+    let mut password_policy = PasswordPolicy::load(config.password_policy.clone())
+        .expect("failed to load common password list");
+    if let Some(path) = &config.hibp_filter_path {
+        let filter = HibpBloomFilter::load(path).expect("failed to load breached password filter");
+        password_policy = password_policy.with_breached_passwords(Arc::new(filter));
+    }
+    let password_policy = Arc::new(password_policy);
     let jwt_service = Arc::new(JwtServiceImpl::new(
         &config.jwt_secret,
         config.jwt_ttl_seconds,
     ));
     let refresh_token_repo = Arc::new(PostgresRefreshTokenRepository::new(db.clone()));
+    let password_history_repo = Arc::new(PostgresPasswordHistoryRepository::new(db.clone()));
 
     let audit_repo = Arc::new(PostgresAuditLogRepository::new(db.clone()));
-    let audit_logger = Arc::new(AuditLogger::new(audit_repo));
+    let audit_logger = Arc::new(AuditLogger::new(audit_repo.clone()));
+
+    let metrics = Arc::new(Metrics::new());
 
-    let rate_limit_store: Arc<dyn RateLimitStore> = if config.use_redis_rate_limit {
-        let redis_client =
-            redis::Client::open(config.redis_url.as_str()).expect("Invalid REDIS_URL");
+    let local_rate_limit_store = Arc::new(InMemoryRateLimitStore::new(
+        config.rate_limit_max_entries,
+        config.rate_limit_shards,
+        metrics.clone(),
+    ));
+    local_rate_limit_store.spawn_sweeper(config.rate_limit_sweep_interval);
 
-        let redis_store = RedisRateLimitStore::new(redis_client)
+    let (rate_limit_store, ban_store): (Arc<dyn RateLimitStore>, Arc<dyn BanStore>) = if config.use_redis_rate_limit {
+        let redis = redis_backends::connect(&config.redis_topology)
             .await
             .expect("failed to connect to redis");
 
-        Arc::new(redis_store)
+        // Both stores talk to the same Redis, so they share one circuit.
+        let breaker = Arc::new(CircuitBreaker::new(
+            config.rate_limit_circuit_failure_threshold,
+            config.rate_limit_circuit_open_duration,
+        ));
+
+        let rate_limit_store = Arc::new(FailoverRateLimitStore::new(
+            redis.rate_limit,
+            config
+                .rate_limit_local_fallback
+                .then(|| local_rate_limit_store.clone() as Arc<dyn RateLimitStore>),
+            breaker.clone(),
+            config.rate_limit_redis_timeout,
+            metrics.clone(),
+        ));
+
+        let ban_store = Arc::new(GuardedBanStore::new(
+            redis.bans,
+            breaker,
+            config.rate_limit_redis_timeout,
+            metrics.clone(),
+        ));
+
+        (rate_limit_store, ban_store)
     } else {
-        Arc::new(InMemoryRateLimitStore::new())
+        (local_rate_limit_store, Arc::new(InMemoryBanStore::new()))
     };
 
+    let rate_limit_policy = RateLimitPolicy::load(config.rate_limit_config_path.as_deref())
+        .expect("failed to load rate limit policy");
+
+    let served_routes = http::routes::served_routes();
+
+    rate_limit_policy
+        .validate_routes(&served_routes)
+        .expect("rate limit policy references an unknown route");
+
+    let login_throttle = Arc::new(LoginThrottle::new(
+        rate_limit_store.clone(),
+        config.login_throttle.clone(),
+    ));
+
+    let auto_ban = Arc::new(AutoBan::new(
+        rate_limit_store.clone(),
+        ban_store.clone(),
+        audit_logger.clone(),
+        config.auto_ban.clone(),
+    ));
+
+    let ip_access = Arc::new(IpAccessList::new(
+        config.ip_allowlist.clone(),
+        config.ip_denylist.clone(),
+    ));
+
+    let concurrency_limiter = Arc::new(ConcurrencyLimiter::new(&config.concurrency));
+
+    for (method, route) in concurrency_limiter.route_limits() {
+        assert!(
+            served_routes
+                .iter()
+                .any(|(m, path)| m == method && path == route),
+            "concurrency limit references unknown route `{method} {route}`"
+        );
+    }
+
+    let client_ip_resolver = Arc::new(ClientIpResolver::new(
+        config.trusted_proxies.clone(),
+        config.trusted_proxy_header,
+    ));
+
     let state = AppState {
         config,
         user_repo,
         refresh_token_repo,
+        audit_log_repo: audit_repo,
+        password_history_repo,
         audit_logger,
+        login_throttle,
         password_hasher,
+        password_policy,
         jwt_service,
         rate_limit_store,
+        rate_limit_policy: Arc::new(rate_limit_policy),
+        client_ip_resolver,
+        ip_access,
+        ban_store,
+        auto_ban,
+        concurrency_limiter,
+        metrics,
     };
 
     let app = http::routes::create_router(state.clone());
@@ -80,7 +203,12 @@ async fn main() {
         .await
         .expect("failed to bind address");
 
-    tracing::info!("🚀 Server up, listening on {}", addr);
+    tracing::info!(
+        app = %state.config.app_name,
+        env = %state.config.env,
+        "🚀 Server up, listening on {}",
+        addr
+    );
 
     axum::serve(
         listener,
@@ -90,6 +218,30 @@ async fn main() {
     .unwrap();
 }
 
+/// `build-hibp-filter <dump> <output> [false-positive-rate]`
+fn build_hibp_filter(args: &[String]) {
+    let [dump, output, rest @ ..] = args else {
+        eprintln!("usage: build-hibp-filter <dump> <output> [false-positive-rate]");
+        std::process::exit(2);
+    };
+
+    let rate = rest
+        .first()
+        .map(|rate| rate.parse().expect("false positive rate must be a number"))
+        .unwrap_or(0.001);
+
+    match HibpBloomFilter::build(dump, output, rate) {
+        Ok(stats) => println!(
+            "wrote {output}: {} hashes, {} lines skipped, {} bytes, {} probes per lookup",
+            stats.entries, stats.skipped, stats.size_bytes, stats.num_hashes
+        ),
+        Err(e) => {
+            eprintln!("failed to build filter: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
 fn init_logging() {
     use once_cell::sync::OnceCell;
     use tracing_appender::non_blocking::WorkerGuard;
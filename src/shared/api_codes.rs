@@ -2,8 +2,16 @@ pub mod health {
     pub const HEALTH_OK: &str = "HEALTH_OK";
 }
 
+pub mod server {
+    pub const OVERLOADED: &str = "SERVER_OVERLOADED";
+    pub const ROUTE_NOT_FOUND: &str = "ROUTE_NOT_FOUND";
+    pub const METHOD_NOT_ALLOWED: &str = "METHOD_NOT_ALLOWED";
+    pub const INTERNAL_ERROR: &str = "INTERNAL_ERROR";
+}
+
 pub mod auth {
     pub const UNAUTHORIZED: &str = "UNAUTHORIZED";
+    pub const FORBIDDEN: &str = "FORBIDDEN";
     pub const INVALID_CREDENTIALS: &str = "INVALID_CREDENTIALS";
     pub const LOGIN_FAILED: &str = "LOGIN_FAILED";
     pub const LOGIN_SUCCESS: &str = "LOGIN_SUCCESS";
@@ -18,10 +26,15 @@ pub mod auth {
     pub const REGISTER_USER_FAILED: &str = "REGISTER_USER_FAILED";
     pub const REGISTER_USER_SUCCESS: &str = "REGISTER_USER_SUCCESS";
     pub const RATE_LIMIT_EXCEEDED: &str = "RATE_LIMIT_EXCEEDED";
+    pub const TOO_MANY_LOGIN_ATTEMPTS: &str = "TOO_MANY_LOGIN_ATTEMPTS";
+    pub const IP_DENIED: &str = "IP_DENIED";
+    pub const IP_BANNED: &str = "IP_BANNED";
+    pub const PASSWORD_CHANGE_REQUIRED: &str = "PASSWORD_CHANGE_REQUIRED";
 }
 
 pub mod users {
     pub const WEAK_PASSWORD: &str = "WEAK_PASSWORD";
+    pub const PASSWORD_REUSED: &str = "PASSWORD_REUSED";
     pub const CHANGE_PASSWORD_FAILED: &str = "CHANGE_PASSWORD_FAILED";
     pub const CHANGE_PASSWORD_SUCCESS: &str = "CHANGE_PASSWORD_SUCCESS";
     pub const USER_NOT_FOUND: &str = "USER_NOT_FOUND";
@@ -29,8 +42,34 @@ pub mod users {
     pub const GET_CURRENT_USER_SUCCESS: &str = "GET_CURRENT_USER_SUCCESS";
     pub const UPDATE_PROFILE_FAILED: &str = "UPDATE_PROFILE_FAILED";
     pub const UPDATE_PROFILE_SUCCESS: &str = "UPDATE_PROFILE_SUCCESS";
+    pub const IMPORT_USERS_SUCCESS: &str = "IMPORT_USERS_SUCCESS";
+    pub const IMPORT_TOO_LARGE: &str = "IMPORT_TOO_LARGE";
+    pub const INVALID_USER_ID: &str = "INVALID_USER_ID";
+    pub const FORCE_PASSWORD_CHANGE_SUCCESS: &str = "FORCE_PASSWORD_CHANGE_SUCCESS";
+    pub const FORCE_PASSWORD_CHANGE_FAILED: &str = "FORCE_PASSWORD_CHANGE_FAILED";
+}
+
+pub mod audit {
+    pub const LIST_AUDIT_LOGS_SUCCESS: &str = "LIST_AUDIT_LOGS_SUCCESS";
+    pub const LIST_AUDIT_LOGS_FAILED: &str = "LIST_AUDIT_LOGS_FAILED";
+    pub const EXPORT_AUDIT_LOGS_FAILED: &str = "EXPORT_AUDIT_LOGS_FAILED";
+    pub const INVALID_EXPORT_FORMAT: &str = "INVALID_EXPORT_FORMAT";
+}
+
+pub mod bans {
+    pub const LIST_BANS_SUCCESS: &str = "LIST_BANS_SUCCESS";
+    pub const LIST_BANS_FAILED: &str = "LIST_BANS_FAILED";
+    pub const LIFT_BAN_SUCCESS: &str = "LIFT_BAN_SUCCESS";
+    pub const LIFT_BAN_FAILED: &str = "LIFT_BAN_FAILED";
+    pub const BAN_NOT_FOUND: &str = "BAN_NOT_FOUND";
+    pub const INVALID_IP_ADDRESS: &str = "INVALID_IP_ADDRESS";
 }
 
 pub mod validator {
     pub const VALIDATION_ERROR: &str = "VALIDATION_ERROR";
+    pub const INVALID_REQUEST_BODY: &str = "INVALID_REQUEST_BODY";
+    pub const MALFORMED_JSON: &str = "MALFORMED_JSON";
+    pub const INVALID_QUERY: &str = "INVALID_QUERY";
+    pub const INVALID_PATH_PARAMETER: &str = "INVALID_PATH_PARAMETER";
+    pub const UNSUPPORTED_MEDIA_TYPE: &str = "UNSUPPORTED_MEDIA_TYPE";
 }
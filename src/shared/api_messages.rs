@@ -2,8 +2,16 @@ pub mod health {
     pub const HEALTH_OK: &str = "Service is running";
 }
 
+pub mod server {
+    pub const OVERLOADED: &str = "server is busy, try again later";
+    pub const ROUTE_NOT_FOUND: &str = "no route matches this path";
+    pub const METHOD_NOT_ALLOWED: &str = "method not allowed for this route";
+    pub const INTERNAL_ERROR: &str = "internal server error";
+}
+
 pub mod auth {
     pub const UNAUTHORIZED: &str = "authentication required";
+    pub const FORBIDDEN: &str = "insufficient permissions";
     pub const UNAUTHORIZED_MISSING_HEADER: &str = "missing authorization header";
     pub const UNAUTHORIZED_INVALID_HEADER: &str = "invalid authorization header";
     pub const UNAUTHORIZED_INVALID_TOKEN: &str = "invalid or expired token";
@@ -21,10 +29,15 @@ pub mod auth {
     pub const REGISTER_USER_FAILED: &str = "failed to register user";
     pub const REGISTER_USER_SUCCESS: &str = "user registered successfully";
     pub const RATE_LIMIT_EXCEEDED: &str = "too many requests";
+    pub const TOO_MANY_LOGIN_ATTEMPTS: &str = "too many login attempts for this account, try again later";
+    pub const IP_DENIED: &str = "requests from this address are not allowed";
+    pub const IP_BANNED: &str = "this address is temporarily banned";
+    pub const PASSWORD_CHANGE_REQUIRED: &str = "password must be changed before continuing";
 }
 
 pub mod users {
     pub const WEAK_PASSWORD: &str = "password does not meet requirements";
+    pub const PASSWORD_REUSED: &str = "new password must differ from your recent passwords";
     pub const CHANGE_PASSWORD_FAILED: &str = "failed to change password";
     pub const CHANGE_PASSWORD_SUCCESS: &str = "password updated successfully";
     pub const USER_NOT_FOUND: &str = "user not found";
@@ -32,11 +45,38 @@ pub mod users {
     pub const GET_CURRENT_USER_SUCCESS: &str = "current user fetched";
     pub const UPDATE_PROFILE_FAILED: &str = "failed to update profile";
     pub const UPDATE_PROFILE_SUCCESS: &str = "profile updated successfully";
+    pub const IMPORT_USERS_SUCCESS: &str = "users imported";
+    pub const IMPORT_TOO_LARGE: &str = "at most 1000 users can be imported at once";
+    pub const INVALID_USER_ID: &str = "invalid user id";
+    pub const FORCE_PASSWORD_CHANGE_SUCCESS: &str = "user must change password at next login";
+    pub const FORCE_PASSWORD_CHANGE_FAILED: &str = "failed to require a password change";
+}
+
+pub mod audit {
+    pub const LIST_AUDIT_LOGS_SUCCESS: &str = "audit logs fetched";
+    pub const LIST_AUDIT_LOGS_FAILED: &str = "failed to fetch audit logs";
+    pub const EXPORT_AUDIT_LOGS_FAILED: &str = "failed to export audit logs";
+    pub const INVALID_EXPORT_FORMAT: &str = "export format must be csv or ndjson";
+}
+
+pub mod bans {
+    pub const LIST_BANS_SUCCESS: &str = "bans fetched";
+    pub const LIST_BANS_FAILED: &str = "failed to fetch bans";
+    pub const LIFT_BAN_SUCCESS: &str = "ban lifted";
+    pub const LIFT_BAN_FAILED: &str = "failed to lift ban";
+    pub const BAN_NOT_FOUND: &str = "no active ban for this address";
+    pub const INVALID_IP_ADDRESS: &str = "invalid IP address";
 }
 
 pub mod validator {
     pub const INVALID_CURRENT_PASSWORD: &str = "invalid current password";
     pub const INVALID_USER_DATA: &str = "invalid user data";
     pub const INVALID_PROFILE_DATA: &str = "invalid profile data";
-    pub const INVALID_PASSWORD_FORMAT: &str = "password must be at least 8 characters";
+    pub const INVALID_REQUEST_BODY: &str = "request body could not be read";
+    pub const MALFORMED_JSON: &str = "request body is not valid JSON";
+    pub const INVALID_QUERY: &str = "invalid query parameters";
+    pub const INVALID_PATH_PARAMETER: &str = "invalid path parameter";
+    pub const UNSUPPORTED_MEDIA_TYPE: &str = "expected a request body with Content-Type: application/json";
+    pub const INVALID_FIELDS: &str = "request contains invalid fields";
+    pub const REQUIRED: &str = "must not be empty";
 }
@@ -1,5 +1,25 @@
+use axum::http::Method;
+use ipnet::IpNet;
+use std::collections::HashMap;
 use std::env;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::auth::login_throttle::LoginThrottleConfig;
+use crate::application::rate_limit::auto_ban::AutoBanConfig;
+use crate::application::security::password_policy::PasswordPolicyConfig;
+use crate::infrastructure::security::argon2_hasher::Argon2Config;
+use crate::http::client_ip::ForwardedHeader;
+use crate::http::concurrency::{ConcurrencyConfig, RouteConcurrencyLimit};
+
+/// How the Redis rate limit store reaches Redis, selected by `REDIS_MODE`.
+#[derive(Clone, Debug)]
+pub enum RedisTopology {
+    Standalone { url: String },
+    Cluster { nodes: Vec<String> },
+    Sentinel { nodes: Vec<String>, master_name: String },
+}
 
 #[derive(Clone, Debug)]
 pub struct AppConfig {
@@ -10,8 +30,120 @@ pub struct AppConfig {
     pub database_url: String,
     pub jwt_ttl_seconds: i64,
     pub refresh_token_ttl_seconds: i64,
-    pub redis_url: String,
+    pub redis_topology: RedisTopology,
     pub use_redis_rate_limit: bool,
+    /// Answer from the in-memory store while Redis is unavailable instead of
+    /// applying each rule's `fail_mode`.
+    pub rate_limit_local_fallback: bool,
+    pub rate_limit_max_entries: usize,
+    pub rate_limit_shards: usize,
+    pub rate_limit_sweep_interval: Duration,
+    pub rate_limit_circuit_failure_threshold: u32,
+    pub rate_limit_circuit_open_duration: Duration,
+    pub rate_limit_redis_timeout: Duration,
+    pub rate_limit_config_path: Option<String>,
+    pub trusted_proxies: Vec<IpNet>,
+    pub trusted_proxy_header: ForwardedHeader,
+    pub ip_allowlist: Vec<IpNet>,
+    pub ip_denylist: Vec<IpNet>,
+    pub auto_ban: AutoBanConfig,
+    pub login_throttle: LoginThrottleConfig,
+    pub concurrency: ConcurrencyConfig,
+    pub password_hash_concurrency: usize,
+    pub argon2: Argon2Config,
+    pub password_policy: PasswordPolicyConfig,
+    /// Bloom filter built with `build-hibp-filter`; the breach check is off
+    /// when unset.
+    pub hibp_filter_path: Option<String>,
+    /// Previous passwords a user may not reuse; 0 disables the check.
+    pub password_history_size: usize,
+    /// Logins with an older password only get a password-change token.
+    pub password_max_age: Option<chrono::Duration>,
+    /// Prefix of problem details `type` URIs, e.g. `https://api.example.com/problems`.
+    pub problem_type_base: Arc<str>,
+    /// Requests slower than this are logged as warnings.
+    pub access_log_slow_threshold: Duration,
+}
+
+fn env_list(name: &str) -> Vec<String> {
+    env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Comma-separated CIDRs or bare addresses.
+fn env_cidrs(name: &str) -> Vec<IpNet> {
+    env_list(name)
+        .iter()
+        .map(|s| {
+            s.parse::<IpNet>()
+                .or_else(|_| s.parse::<IpAddr>().map(IpNet::from))
+                .unwrap_or_else(|_| panic!("{name} must be a list of IPs or CIDRs"))
+        })
+        .collect()
+}
+
+/// Comma-separated `id=secret` entries for retired password peppers.
+fn env_peppers(name: &str) -> HashMap<String, String> {
+    env_list(name)
+        .iter()
+        .map(|entry| {
+            let (id, secret) = entry
+                .split_once('=')
+                .filter(|(id, secret)| !id.is_empty() && !secret.is_empty())
+                .unwrap_or_else(|| panic!("{name} must be a list of id=secret entries"));
+
+            (id.to_string(), secret.to_string())
+        })
+        .collect()
+}
+
+/// Comma-separated `METHOD /route=N` entries.
+fn parse_route_limits(raw: &str) -> Vec<RouteConcurrencyLimit> {
+    fn invalid<T>(entry: &str) -> T {
+        panic!("invalid CONCURRENCY_ROUTE_LIMITS entry `{entry}`")
+    }
+
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|entry| {
+            let (target, limit) = entry.rsplit_once('=').unwrap_or_else(|| invalid(entry));
+            let (method, route) = target.trim().split_once(' ').unwrap_or_else(|| invalid(entry));
+
+            RouteConcurrencyLimit {
+                method: Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                    .unwrap_or_else(|_| invalid(entry)),
+                route: route.trim().to_string(),
+                max_in_flight: limit.trim().parse().unwrap_or_else(|_| invalid(entry)),
+            }
+        })
+        .collect()
+}
+
+fn env_bool(name: &str, default: bool) -> bool {
+    env::var(name)
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(default)
+}
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .map(|v| v.parse().unwrap_or_else(|_| panic!("{name} must be a number")))
+        .unwrap_or(default)
+}
+
+/// For limits, windows and intervals, where zero would divide by zero,
+/// reject everything or panic a timer.
+fn env_nonzero_u64(name: &str, default: u64) -> u64 {
+    let value = env_u64(name, default);
+    assert!(value > 0, "{name} must be greater than zero");
+    value
 }
 
 impl AppConfig {
@@ -45,10 +177,119 @@ impl AppConfig {
         let redis_url = env::var("REDIS_URL")
             .unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
 
+        let redis_topology = match env::var("REDIS_MODE").as_deref() {
+            Ok("cluster") => RedisTopology::Cluster {
+                nodes: env_list("REDIS_CLUSTER_NODES"),
+            },
+            Ok("sentinel") => RedisTopology::Sentinel {
+                nodes: env_list("REDIS_SENTINEL_NODES"),
+                master_name: env::var("REDIS_SENTINEL_MASTER")
+                    .expect("REDIS_SENTINEL_MASTER must be set when REDIS_MODE=sentinel"),
+            },
+            Ok("standalone") | Err(_) => RedisTopology::Standalone {
+                url: redis_url,
+            },
+            Ok(other) => panic!("unknown REDIS_MODE `{other}`"),
+        };
+
         let use_redis_rate_limit = env::var("USE_REDIS_RATE_LIMIT")
             .map(|v| v == "true" || v == "1")
             .unwrap_or(false);
 
+        // Bounds for the in-memory rate limit store.
+        let rate_limit_max_entries = env_u64("RATE_LIMIT_MAX_ENTRIES", 100_000) as usize;
+        let rate_limit_shards = env_u64("RATE_LIMIT_SHARDS", 16) as usize;
+        let rate_limit_sweep_interval =
+            Duration::from_secs(env_nonzero_u64("RATE_LIMIT_SWEEP_INTERVAL_SECS", 30));
+
+        // Circuit breaker in front of the Redis rate limit and ban stores.
+        let rate_limit_circuit_failure_threshold =
+            env_u64("RATE_LIMIT_CIRCUIT_FAILURE_THRESHOLD", 5) as u32;
+        let rate_limit_circuit_open_duration =
+            Duration::from_secs(env_u64("RATE_LIMIT_CIRCUIT_OPEN_SECS", 30));
+        let rate_limit_redis_timeout =
+            Duration::from_millis(env_u64("RATE_LIMIT_REDIS_TIMEOUT_MS", 200));
+
+        let rate_limit_config_path = env::var("RATE_LIMIT_CONFIG_PATH").ok();
+
+        // Reverse proxies whose forwarding headers are trusted.
+        let trusted_proxies = env_cidrs("TRUSTED_PROXIES");
+
+        // Which header those proxies set. Only that one is read, so it must
+        // be named explicitly whenever proxies are trusted.
+        let trusted_proxy_header = match env::var("TRUSTED_PROXY_HEADER") {
+            Ok(value) => ForwardedHeader::parse(&value).unwrap_or_else(|| {
+                panic!("TRUSTED_PROXY_HEADER must be `forwarded` or `x-forwarded-for`")
+            }),
+            Err(_) if trusted_proxies.is_empty() => ForwardedHeader::default(),
+            Err(_) => panic!("TRUSTED_PROXY_HEADER must be set when TRUSTED_PROXIES is"),
+        };
+
+        let ip_allowlist = env_cidrs("IP_ALLOWLIST");
+        let ip_denylist = env_cidrs("IP_DENYLIST");
+
+        let auto_ban = AutoBanConfig {
+            threshold: env_u64("AUTO_BAN_THRESHOLD", 10) as u32,
+            window: Duration::from_secs(env_u64("AUTO_BAN_WINDOW_SECS", 600)),
+            duration: Duration::from_secs(env_u64("AUTO_BAN_DURATION_SECS", 3600)),
+        };
+
+        let login_throttle = LoginThrottleConfig {
+            account_limit: env_nonzero_u64("LOGIN_ACCOUNT_LIMIT", 10) as u32,
+            strict_account_limit: env_nonzero_u64("LOGIN_STRICT_ACCOUNT_LIMIT", 3) as u32,
+            account_window: Duration::from_secs(env_nonzero_u64("LOGIN_ACCOUNT_WINDOW_SECS", 900)),
+            failure_threshold: env_nonzero_u64("LOGIN_FAILURE_THRESHOLD", 100) as u32,
+            failure_window: Duration::from_secs(env_nonzero_u64("LOGIN_FAILURE_WINDOW_SECS", 60)),
+            strict_cooldown: Duration::from_secs(env_u64("LOGIN_STRICT_COOLDOWN_SECS", 900)),
+        };
+
+        // Routes hashing passwords are capped near the core count by default,
+        // so a burst of logins cannot starve every other request.
+        let hashing_slots = std::thread::available_parallelism().map_or(4, |n| n.get() * 2);
+        let route_limits = env::var("CONCURRENCY_ROUTE_LIMITS").unwrap_or_else(|_| {
+            format!(
+                "POST /auth/login={hashing_slots},\
+                 POST /auth/register={hashing_slots},\
+                 PUT /users/me/change-password={hashing_slots}"
+            )
+        });
+
+        // Hashes running at once on the blocking pool; more would only
+        // contend for the same cores.
+        let password_hash_concurrency = env_u64(
+            "PASSWORD_HASH_CONCURRENCY",
+            std::thread::available_parallelism().map_or(4, |n| n.get() as u64),
+        ) as usize;
+
+        let argon2_defaults = Argon2Config::default();
+        let argon2 = Argon2Config {
+            memory_kib: env_u64("ARGON2_MEMORY_KIB", argon2_defaults.memory_kib.into()) as u32,
+            iterations: env_u64("ARGON2_ITERATIONS", argon2_defaults.iterations.into()) as u32,
+            parallelism: env_u64("ARGON2_PARALLELISM", argon2_defaults.parallelism.into()) as u32,
+            pepper: env::var("PASSWORD_PEPPER").ok().filter(|p| !p.is_empty()),
+            pepper_id: env::var("PASSWORD_PEPPER_ID").unwrap_or(argon2_defaults.pepper_id),
+            previous_peppers: env_peppers("PASSWORD_PREVIOUS_PEPPERS"),
+        };
+
+        let password_policy = PasswordPolicyConfig {
+            min_length: env_u64("PASSWORD_MIN_LENGTH", 8) as usize,
+            max_length: env_u64("PASSWORD_MAX_LENGTH", 128) as usize,
+            require_lowercase: env_bool("PASSWORD_REQUIRE_LOWERCASE", false),
+            require_uppercase: env_bool("PASSWORD_REQUIRE_UPPERCASE", false),
+            require_digit: env_bool("PASSWORD_REQUIRE_DIGIT", false),
+            require_symbol: env_bool("PASSWORD_REQUIRE_SYMBOL", false),
+            min_entropy_bits: env_u64("PASSWORD_MIN_ENTROPY_BITS", 40) as f64,
+            common_list_path: env::var("PASSWORD_COMMON_LIST_PATH").ok(),
+        };
+
+        let concurrency = ConcurrencyConfig {
+            max_in_flight: env_u64("CONCURRENCY_MAX_IN_FLIGHT", 1024) as usize,
+            max_queued: env_u64("CONCURRENCY_MAX_QUEUED", 256) as usize,
+            queue_timeout: Duration::from_millis(env_u64("CONCURRENCY_QUEUE_TIMEOUT_MS", 2000)),
+            retry_after: Duration::from_secs(env_u64("CONCURRENCY_RETRY_AFTER_SECS", 1)),
+            routes: parse_route_limits(&route_limits),
+        };
+
         Self {
             app_name,
             env: env_name,
@@ -57,8 +298,39 @@ impl AppConfig {
             database_url,
             jwt_ttl_seconds,
             refresh_token_ttl_seconds,
-            redis_url,
+            redis_topology,
             use_redis_rate_limit,
+            rate_limit_local_fallback: env_bool("RATE_LIMIT_LOCAL_FALLBACK", false),
+            rate_limit_max_entries,
+            rate_limit_shards,
+            rate_limit_sweep_interval,
+            rate_limit_circuit_failure_threshold,
+            rate_limit_circuit_open_duration,
+            rate_limit_redis_timeout,
+            rate_limit_config_path,
+            trusted_proxies,
+            trusted_proxy_header,
+            ip_allowlist,
+            ip_denylist,
+            auto_ban,
+            login_throttle,
+            concurrency,
+            password_hash_concurrency,
+            argon2,
+            password_policy,
+            hibp_filter_path: env::var("HIBP_FILTER_PATH").ok(),
+            password_history_size: env_u64("PASSWORD_HISTORY_SIZE", 5) as usize,
+            access_log_slow_threshold: Duration::from_millis(env_u64(
+                "ACCESS_LOG_SLOW_THRESHOLD_MS",
+                1000,
+            )),
+            problem_type_base: env::var("PROBLEM_TYPE_BASE_URI")
+                .unwrap_or_else(|_| "/problems".to_string())
+                .into(),
+            password_max_age: match env_u64("PASSWORD_MAX_AGE_DAYS", 0) {
+                0 => None,
+                days => Some(chrono::Duration::days(days as i64)),
+            },
         }
     }
 }
@@ -1,14 +1,20 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+use crate::shared::request_id;
+
+#[derive(Serialize, ToSchema)]
 pub struct ApiErrorResponse {
     pub success: bool,
     pub code: String,
     pub message: String,
     pub errors: Option<ErrorDetails>,
+    /// Matches the `X-Request-Id` response header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ErrorDetails {
     pub fields: Option<std::collections::HashMap<String, Vec<String>>>,
 }
@@ -24,6 +30,7 @@ impl ApiErrorResponse {
             code: code.into(),
             message: message.into(),
             errors,
+            request_id: current_request_id(),
         }
     }
 
@@ -33,6 +40,11 @@ impl ApiErrorResponse {
             code: code.into(),
             message: message.into(),
             errors: None,
+            request_id: current_request_id(),
         }
     }
 }
+
+fn current_request_id() -> Option<String> {
+    request_id::current().map(|id| id.to_string())
+}
@@ -0,0 +1,99 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+/// Process-wide counters and gauges, rendered in the Prometheus text format
+/// by `GET /metrics`.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    pub rate_limit_buckets: AtomicI64,
+    pub rate_limit_evictions: AtomicU64,
+    pub rate_limit_expired: AtomicU64,
+    pub rate_limit_circuit_state: AtomicI64,
+    pub rate_limit_circuit_transitions: AtomicU64,
+    pub rate_limit_fallback_checks: AtomicU64,
+    pub rate_limit_store_errors: AtomicU64,
+    pub concurrency_in_flight: AtomicI64,
+    pub concurrency_queued: AtomicI64,
+    pub concurrency_shed: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        gauge(
+            &mut out,
+            "rate_limit_buckets",
+            "Rate limit buckets held in memory.",
+            self.rate_limit_buckets.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "rate_limit_evictions_total",
+            "Rate limit buckets evicted to stay under the entry cap.",
+            self.rate_limit_evictions.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "rate_limit_expired_total",
+            "Expired rate limit buckets removed by the sweeper.",
+            self.rate_limit_expired.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "rate_limit_circuit_state",
+            "Rate limit store circuit: 0 closed, 1 open, 2 half-open.",
+            self.rate_limit_circuit_state.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "rate_limit_circuit_transitions_total",
+            "Rate limit store circuit state changes.",
+            self.rate_limit_circuit_transitions.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "rate_limit_fallback_checks_total",
+            "Rate limit checks answered by the local fallback store.",
+            self.rate_limit_fallback_checks.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "rate_limit_store_errors_total",
+            "Rate limit checks that failed and were resolved by the rule's fail mode.",
+            self.rate_limit_store_errors.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "http_requests_in_flight",
+            "Requests holding a concurrency slot.",
+            self.concurrency_in_flight.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "http_requests_queued",
+            "Requests waiting for a concurrency slot.",
+            self.concurrency_queued.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "http_requests_shed_total",
+            "Requests rejected with 503 because no concurrency slot was available.",
+            self.concurrency_shed.load(Ordering::Relaxed),
+        );
+
+        out
+    }
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: i64) {
+    let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}");
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} counter\n{name} {value}");
+}
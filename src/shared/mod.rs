@@ -3,4 +3,6 @@ pub mod error;
 pub mod config;
 pub mod state;
 pub mod api_codes;
-pub mod api_messages;
\ No newline at end of file
+pub mod api_messages;
+pub mod metrics;
+pub mod request_id;
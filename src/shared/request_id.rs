@@ -0,0 +1,17 @@
+use std::future::Future;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static REQUEST_ID: Uuid;
+}
+
+/// Id of the request the current task is serving, set by
+/// `request_id_middleware`. `None` outside of a request and in tasks spawned
+/// from one.
+pub fn current() -> Option<Uuid> {
+    REQUEST_ID.try_with(|id| *id).ok()
+}
+
+pub async fn scope<F: Future>(id: Uuid, f: F) -> F::Output {
+    REQUEST_ID.scope(id, f).await
+}
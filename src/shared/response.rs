@@ -1,6 +1,7 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub code: String,
@@ -9,12 +10,12 @@ pub struct ApiResponse<T> {
     pub meta: Option<Meta>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct Meta {
     pub pagination: Option<PaginationMeta>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct PaginationMeta {
     pub page: u32,
     pub per_page: u32,
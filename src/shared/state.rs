@@ -1,13 +1,23 @@
 use std::sync::Arc;
 
 use crate::application::audit::audit_logger::AuditLogger;
+use crate::application::auth::login_throttle::LoginThrottle;
+use crate::application::rate_limit::auto_ban::AutoBan;
+use crate::application::security::password_policy::PasswordPolicy;
+use crate::domain::rate_limit::ban_store::BanStore;
+use crate::domain::audit::repository::AuditLogRepository;
 use crate::domain::auth::repository::RefreshTokenRepository;
 use crate::{
     application::security::{jwt::JwtService, password_hasher::PasswordHasher},
-    domain::user::repository::UserRepository,
+    domain::user::{password_history::PasswordHistoryRepository, repository::UserRepository},
     shared::config::AppConfig,
 };
 use crate::domain::rate_limit::store::RateLimitStore;
+use crate::http::client_ip::ClientIpResolver;
+use crate::http::concurrency::ConcurrencyLimiter;
+use crate::http::ip_access::IpAccessList;
+use crate::http::middleware::rate_limit_policy::RateLimitPolicy;
+use crate::shared::metrics::Metrics;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -15,10 +25,21 @@ pub struct AppState {
 
     pub user_repo: Arc<dyn UserRepository>,
     pub refresh_token_repo: Arc<dyn RefreshTokenRepository>,
+    pub audit_log_repo: Arc<dyn AuditLogRepository>,
+    pub password_history_repo: Arc<dyn PasswordHistoryRepository>,
 
     pub password_hasher: Arc<dyn PasswordHasher>,
     pub jwt_service: Arc<dyn JwtService>,
+    pub password_policy: Arc<PasswordPolicy>,
 
     pub audit_logger: Arc<AuditLogger>,
+    pub login_throttle: Arc<LoginThrottle>,
     pub rate_limit_store: Arc<dyn RateLimitStore>,
+    pub rate_limit_policy: Arc<RateLimitPolicy>,
+    pub client_ip_resolver: Arc<ClientIpResolver>,
+    pub ip_access: Arc<IpAccessList>,
+    pub ban_store: Arc<dyn BanStore>,
+    pub auto_ban: Arc<AutoBan>,
+    pub concurrency_limiter: Arc<ConcurrencyLimiter>,
+    pub metrics: Arc<Metrics>,
 }